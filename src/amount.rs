@@ -13,13 +13,21 @@
 // limitations under the License.
 
 use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::integer::IntegerRepr;
+use crate::retag::Retag;
+use crate::rounding::{div_round, div_round_unsigned, from_i128, to_i128, Rounding};
+use crate::units::{UnitDiv, UnitMul};
 #[cfg(feature="serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::Ordering;
+use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::iter::Sum;
 use std::marker::PhantomData;
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
 
 /// `Amount<Unit>` provides a type-safe way to keep an amount of
 /// some `Unit`.
@@ -73,9 +81,10 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 /// assert_eq!(y - y, Amount::<Oranges, u64>::from(0));
 /// ```
 ///
-/// Multiplication of amounts is not supported: multiplying meters by
-/// meters gives square meters. However, you can scale an amount by a
-/// scalar or divide amounts:
+/// Multiplication of amounts is only supported for units opted into
+/// the unit algebra (see [`BaseUnit`](trait.BaseUnit.html)):
+/// multiplying meters by meters gives square meters. However, you can
+/// always scale an amount by a scalar or divide amounts:
 ///
 /// ```
 /// use phantom_newtype::Amount;
@@ -84,10 +93,34 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 ///
 /// let x = Amount::<Apples, u64>::from(3);
 /// assert_eq!(x * 3, Amount::<Apples, u64>::from(9));
+/// assert_eq!(3 * x, Amount::<Apples, u64>::from(9));
 /// assert_eq!(1, x / x);
 /// assert_eq!(3, (x * 3) / x);
 /// ```
 ///
+/// Dividing an amount by a scalar gives an amount, the remainder of a
+/// division is an amount as well:
+///
+/// ```
+/// use phantom_newtype::Amount;
+///
+/// enum Cycles {}
+/// type CycleCount = Amount<Cycles, i64>;
+///
+/// let budget = CycleCount::from(1000);
+///
+/// assert_eq!(budget / 3, CycleCount::from(333));
+/// assert_eq!(budget % 3, CycleCount::from(1));
+/// assert_eq!(budget % CycleCount::from(300), CycleCount::from(100));
+/// assert_eq!(budget.div_rem(3), (CycleCount::from(333), CycleCount::from(1)));
+/// assert_eq!(-budget, CycleCount::from(-1000));
+///
+/// assert_eq!(budget.div_floor(-3), CycleCount::from(-334));
+/// assert_eq!(budget.div_ceil(3), CycleCount::from(334));
+/// assert_eq!(CycleCount::from(10).div_round_half_even(4), CycleCount::from(2));
+/// assert_eq!(CycleCount::from(14).div_round_half_even(4), CycleCount::from(4));
+/// ```
+///
 /// Note that the unit is only available at compile time, thus using
 /// `Amount` instead of `u64` doesn't incur any runtime penalty:
 ///
@@ -130,6 +163,20 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 /// assert!(ASTRONOMICAL_UNIT > Distance::from(0));
 /// ```
 ///
+/// Amounts of primitive types have `ZERO`, `ONE`, `MIN` and `MAX`
+/// constants:
+///
+/// ```
+/// use phantom_newtype::Amount;
+///
+/// enum Bytes {}
+/// type Size = Amount<Bytes, u32>;
+///
+/// assert_eq!(Size::ZERO, Size::from(0));
+/// assert_eq!(Size::ONE + Size::ONE, Size::from(2));
+/// assert_eq!(Size::MAX.checked_add(Size::ONE), None);
+/// ```
+///
 /// Amounts can be sent between threads if the `Repr` allows it, no
 /// matter which `Unit` is used.
 ///
@@ -168,6 +215,41 @@ impl<Unit, Repr> Amount<Unit, Repr> {
     pub const fn new(repr: Repr) -> Amount<Unit, Repr> {
         Amount(repr, PhantomData)
     }
+
+    /// Converts the amount into an amount of another unit. The conversion
+    /// must be allowed by a [`Retag`](trait.Retag.html) implementation.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Retag};
+    ///
+    /// enum ReceivedBytes {}
+    /// enum VerifiedBytes {}
+    /// impl Retag<VerifiedBytes> for ReceivedBytes {}
+    ///
+    /// let received = Amount::<ReceivedBytes, u64>::from(512);
+    /// let verified: Amount<VerifiedBytes, u64> = received.retag();
+    /// assert_eq!(verified, Amount::from(512));
+    /// ```
+    pub fn retag<To>(self) -> Amount<To, Repr>
+    where
+        Unit: Retag<To>,
+    {
+        Amount::new(self.0)
+    }
+
+    /// Drops the unit and returns the underlying representation.
+    ///
+    /// ```
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Bytes {}
+    ///
+    /// let len = Amount::<Bytes, usize>::from(3);
+    /// assert_eq!(vec![0u8; len.forget_tag()].len(), 3);
+    /// ```
+    pub fn forget_tag(self) -> Repr {
+        self.0
+    }
 }
 
 impl<Unit: Default, Repr: Copy> Amount<Unit, Repr> {
@@ -215,6 +297,261 @@ where
     }
 }
 
+impl<Unit, Repr> Amount<Unit, Repr>
+where
+    Repr: Div + Copy,
+{
+    /// Divides this amount by an amount of another unit, computing
+    /// the unit of the result using the type-level unit algebra (see
+    /// [`BaseUnit`](trait.BaseUnit.html)).
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, BaseUnit, Per};
+    ///
+    /// enum Meters {}
+    /// enum Seconds {}
+    ///
+    /// impl BaseUnit for Meters {}
+    /// impl BaseUnit for Seconds {}
+    ///
+    /// let speed = Amount::<Meters, u64>::from(100).divide(Amount::<Seconds, u64>::from(20));
+    /// assert_eq!(speed, Amount::<Per<Meters, Seconds>, u64>::from(5));
+    /// ```
+    pub fn divide<Unit2>(
+        self,
+        rhs: Amount<Unit2, Repr>,
+    ) -> Amount<<Unit as UnitDiv<Unit2>>::Output, <Repr as Div>::Output>
+    where
+        Unit: UnitDiv<Unit2>,
+    {
+        Amount::new(self.0 / rhs.get())
+    }
+}
+
+impl<Unit, Repr: IntegerRepr> Amount<Unit, Repr> {
+    /// Adds amounts returning `None` on overflow.
+    ///
+    /// ```
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Cycles {}
+    /// type CycleCount = Amount<Cycles, u8>;
+    ///
+    /// let x = CycleCount::from(200);
+    ///
+    /// assert_eq!(x.checked_add(CycleCount::from(50)), Some(CycleCount::from(250)));
+    /// assert_eq!(x.checked_add(CycleCount::from(100)), None);
+    /// assert_eq!(x.saturating_add(CycleCount::from(100)), CycleCount::from(255));
+    /// assert_eq!(x.wrapping_add(CycleCount::from(100)), CycleCount::from(44));
+    /// assert_eq!(x.overflowing_add(CycleCount::from(100)), (CycleCount::from(44), true));
+    /// assert_eq!(x.checked_sub(CycleCount::from(201)), None);
+    /// assert_eq!(x.saturating_mul(2), CycleCount::from(255));
+    /// ```
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self::new)
+    }
+
+    /// Subtracts amounts returning `None` on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self::new)
+    }
+
+    /// Multiplies the amount by a scalar returning `None` on overflow.
+    pub fn checked_mul(self, rhs: Repr) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self::new)
+    }
+
+    /// Adds amounts saturating at the bounds of `Repr`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self::new(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtracts amounts saturating at the bounds of `Repr`.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self::new(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiplies the amount by a scalar saturating at the bounds of
+    /// `Repr`.
+    pub fn saturating_mul(self, rhs: Repr) -> Self {
+        Self::new(self.0.saturating_mul(rhs))
+    }
+
+    /// Adds amounts wrapping around at the bounds of `Repr`.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self::new(self.0.wrapping_add(rhs.0))
+    }
+
+    /// Subtracts amounts wrapping around at the bounds of `Repr`.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::new(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// Multiplies the amount by a scalar wrapping around at the bounds of
+    /// `Repr`.
+    pub fn wrapping_mul(self, rhs: Repr) -> Self {
+        Self::new(self.0.wrapping_mul(rhs))
+    }
+
+    /// Adds amounts returning the wrapped result and whether an overflow
+    /// occurred.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (repr, overflow) = self.0.overflowing_add(rhs.0);
+        (Self::new(repr), overflow)
+    }
+
+    /// Subtracts amounts returning the wrapped result and whether an
+    /// overflow occurred.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (repr, overflow) = self.0.overflowing_sub(rhs.0);
+        (Self::new(repr), overflow)
+    }
+
+    /// Multiplies the amount by a scalar returning the wrapped result and
+    /// whether an overflow occurred.
+    pub fn overflowing_mul(self, rhs: Repr) -> (Self, bool) {
+        let (repr, overflow) = self.0.overflowing_mul(rhs);
+        (Self::new(repr), overflow)
+    }
+}
+
+impl<Unit, Repr: IntegerRepr + Default> Amount<Unit, Repr> {
+    /// Sums amounts, returns `None` on overflow. Like `Sum`, widens the
+    /// representation if the target `Repr` can be constructed from the
+    /// source one.
+    ///
+    /// ```
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Satoshi {}
+    /// type Balance = Amount<Satoshi, u32>;
+    ///
+    /// let balances = vec![Balance::from(u32::MAX), Balance::from(1)];
+    ///
+    /// assert_eq!(Balance::try_sum(balances.clone()), None);
+    /// assert_eq!(
+    ///     Amount::<Satoshi, u64>::try_sum(balances),
+    ///     Some(Amount::from(u64::from(u32::MAX) + 1))
+    /// );
+    /// ```
+    pub fn try_sum<I, Repr2>(iter: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Amount<Unit, Repr2>>,
+        Repr: From<Repr2>,
+    {
+        iter.into_iter()
+            .try_fold(Repr::default(), |acc, x| acc.checked_add(Repr::from(x.0)))
+            .map(Self::new)
+    }
+}
+
+macro_rules! impl_primitive_consts {
+    ($($repr:ident($zero:expr, $one:expr)),*) => {
+        $(
+            impl<Unit> Amount<Unit, $repr> {
+                /// The amount of zero units.
+                pub const ZERO: Self = Self::new($zero);
+                /// The amount of one unit.
+                pub const ONE: Self = Self::new($one);
+                /// The smallest representable amount.
+                pub const MIN: Self = Self::new($repr::MIN);
+                /// The largest representable amount.
+                pub const MAX: Self = Self::new($repr::MAX);
+            }
+        )*
+    };
+}
+
+impl_primitive_consts!(
+    u8(0, 1),
+    u16(0, 1),
+    u32(0, 1),
+    u64(0, 1),
+    u128(0, 1),
+    usize(0, 1),
+    i8(0, 1),
+    i16(0, 1),
+    i32(0, 1),
+    i64(0, 1),
+    i128(0, 1),
+    isize(0, 1),
+    f32(0.0, 1.0),
+    f64(0.0, 1.0)
+);
+
+impl<Unit, Repr> Amount<Unit, Repr>
+where
+    Repr: Div<Output = Repr> + Rem<Output = Repr> + Copy,
+{
+    /// Divides the amount by a scalar, returns the quotient and the
+    /// remainder.
+    pub fn div_rem(self, rhs: Repr) -> (Self, Self) {
+        (Self::new(self.0 / rhs), Self::new(self.0 % rhs))
+    }
+}
+
+impl<Unit, Repr> Amount<Unit, Repr>
+where
+    Repr: TryFrom<i128> + TryInto<i128> + TryFrom<u128> + TryInto<u128> + Copy,
+{
+    /// Divides the amount by a scalar rounding the result according to
+    /// `rounding`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero or the quotient doesn't fit into `Repr`,
+    /// e.g. when dividing `i64::MIN` by `-1`.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Rounding};
+    ///
+    /// enum Wei {}
+    ///
+    /// let x = Amount::<Wei, u128>::from(u128::MAX);
+    /// assert_eq!(x.div_floor(2), Amount::from(u128::MAX / 2));
+    /// assert_eq!(x.div_rounded(2, Rounding::Ceil), Amount::from(1 << 127));
+    ///
+    /// let y = Amount::<Wei, i128>::from(i128::MIN);
+    /// assert_eq!(y.div_floor(-2), Amount::from(1 << 126));
+    /// ```
+    pub fn div_rounded(self, rhs: Repr, rounding: Rounding) -> Self {
+        let unsigned = (
+            TryInto::<u128>::try_into(self.0),
+            TryInto::<u128>::try_into(rhs),
+        );
+        match unsigned {
+            (Ok(n), Ok(d)) => Self::new(
+                Repr::try_from(div_round_unsigned(n, d, rounding))
+                    .ok()
+                    .expect("value doesn't fit into the representation"),
+            ),
+            _ => Self::new(from_i128(div_round(
+                to_i128(self.0),
+                to_i128(rhs),
+                rounding,
+            ))),
+        }
+    }
+
+    /// Divides the amount by a scalar rounding the result towards
+    /// negative infinity.
+    pub fn div_floor(self, rhs: Repr) -> Self {
+        self.div_rounded(rhs, Rounding::Floor)
+    }
+
+    /// Divides the amount by a scalar rounding the result towards
+    /// positive infinity.
+    pub fn div_ceil(self, rhs: Repr) -> Self {
+        self.div_rounded(rhs, Rounding::Ceil)
+    }
+
+    /// Divides the amount by a scalar rounding the result to the
+    /// nearest integer, ties are rounded to the even one.
+    pub fn div_round_half_even(self, rhs: Repr) -> Self {
+        self.div_rounded(rhs, Rounding::HalfEven)
+    }
+}
+
 impl<Unit, Repr: Copy> From<Repr> for Amount<Unit, Repr> {
     fn from(repr: Repr) -> Self {
         Self::new(repr)
@@ -229,7 +566,7 @@ impl<Unit, Repr: Copy> From<Repr> for Amount<Unit, Repr> {
 
 impl<Unit, Repr: Copy> Clone for Amount<Unit, Repr> {
     fn clone(&self) -> Self {
-        Amount(self.0, PhantomData)
+        *self
     }
 }
 
@@ -323,6 +660,18 @@ where
     }
 }
 
+impl<Unit, Unit2, Repr> Mul<Amount<Unit2, Repr>> for Amount<Unit, Repr>
+where
+    Unit: UnitMul<Unit2>,
+    Repr: Mul + Copy,
+{
+    type Output = Amount<<Unit as UnitMul<Unit2>>::Output, <Repr as Mul>::Output>;
+
+    fn mul(self, rhs: Amount<Unit2, Repr>) -> Self::Output {
+        Amount::new(self.0 * rhs.get())
+    }
+}
+
 impl<Unit, Repr> Div<Self> for Amount<Unit, Repr>
 where
     Repr: Div<Repr> + Copy,
@@ -334,6 +683,143 @@ where
     }
 }
 
+impl<Unit, Repr> DivAssign<Repr> for Amount<Unit, Repr>
+where
+    Repr: DivAssign + Copy,
+{
+    fn div_assign(&mut self, rhs: Repr) {
+        self.0 /= rhs;
+    }
+}
+
+impl<Unit, Repr> Div<Repr> for Amount<Unit, Repr>
+where
+    Repr: DivAssign + Copy,
+{
+    type Output = Self;
+
+    fn div(mut self, rhs: Repr) -> Self {
+        self.div_assign(rhs);
+        self
+    }
+}
+
+impl<Unit, Repr> RemAssign<Repr> for Amount<Unit, Repr>
+where
+    Repr: RemAssign + Copy,
+{
+    fn rem_assign(&mut self, rhs: Repr) {
+        self.0 %= rhs;
+    }
+}
+
+impl<Unit, Repr> Rem<Repr> for Amount<Unit, Repr>
+where
+    Repr: RemAssign + Copy,
+{
+    type Output = Self;
+
+    fn rem(mut self, rhs: Repr) -> Self {
+        self.rem_assign(rhs);
+        self
+    }
+}
+
+impl<Unit, Repr> RemAssign for Amount<Unit, Repr>
+where
+    Repr: RemAssign + Copy,
+{
+    fn rem_assign(&mut self, rhs: Self) {
+        self.0 %= rhs.0;
+    }
+}
+
+impl<Unit, Repr> Rem for Amount<Unit, Repr>
+where
+    Repr: RemAssign + Copy,
+{
+    type Output = Self;
+
+    fn rem(mut self, rhs: Self) -> Self {
+        self.rem_assign(rhs);
+        self
+    }
+}
+
+impl<Unit, Repr> Neg for Amount<Unit, Repr>
+where
+    Repr: Neg<Output = Repr> + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.0)
+    }
+}
+
+/// Summing amounts widens the representation if the target `Repr`
+/// can be constructed from the source one:
+///
+/// ```
+/// use phantom_newtype::Amount;
+///
+/// enum Satoshi {}
+///
+/// let balances = vec![Amount::<Satoshi, u32>::from(u32::MAX); 3];
+///
+/// let total: Amount<Satoshi, u64> = balances.iter().sum();
+/// assert_eq!(total, Amount::from(3 * u64::from(u32::MAX)));
+/// assert_eq!(balances[..1].iter().sum::<Amount<Satoshi, u32>>(), balances[0]);
+/// assert_eq!(balances.into_iter().sum::<Amount<Satoshi, u64>>(), total);
+/// ```
+///
+/// Amounts don't implement `Product`, the product of amounts has a
+/// different unit:
+///
+/// ```compile_fail
+/// use phantom_newtype::Amount;
+///
+/// enum Apples {}
+///
+/// let p: Amount<Apples, u64> = vec![Amount::from(2), Amount::from(3)].into_iter().product();
+/// ```
+impl<Unit, Repr, Repr2> Sum<Amount<Unit, Repr2>> for Amount<Unit, Repr>
+where
+    Repr: Sum + From<Repr2>,
+{
+    fn sum<I: Iterator<Item = Amount<Unit, Repr2>>>(iter: I) -> Self {
+        Self::new(iter.map(|x| Repr::from(x.0)).sum())
+    }
+}
+
+impl<'a, Unit, Repr, Repr2> Sum<&'a Amount<Unit, Repr2>> for Amount<Unit, Repr>
+where
+    Repr: Sum + From<Repr2>,
+    Repr2: Copy,
+{
+    fn sum<I: Iterator<Item = &'a Amount<Unit, Repr2>>>(iter: I) -> Self {
+        Self::new(iter.map(|x| Repr::from(x.0)).sum())
+    }
+}
+
+// Scalars can't be multiplied by amounts generically: `impl<Unit, Repr>
+// Mul<Amount<Unit, Repr>> for Repr` violates the orphan rules.
+macro_rules! impl_scalar_mul {
+    ($($repr:ident),*) => {
+        $(
+            impl<Unit> Mul<Amount<Unit, $repr>> for $repr {
+                type Output = Amount<Unit, $repr>;
+
+                fn mul(self, rhs: Amount<Unit, $repr>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
 impl<Unit, Repr> fmt::Debug for Amount<Unit, Repr>
 where
     Repr: fmt::Debug,
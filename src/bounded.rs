@@ -0,0 +1,397 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::sealed::Sealed;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::TryInto;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// `Bounded<Unit, Repr, MIN, MAX>` is an amount of some `Unit` that is
+/// guaranteed to lie within `[MIN, MAX]`. Both bounds are inclusive
+/// and must be representable by `Repr`.
+///
+/// Bounded amounts can only be constructed using `try_new`:
+///
+/// ```
+/// use phantom_newtype::Bounded;
+///
+/// enum PercentUnit {}
+/// type Percent = Bounded<PercentUnit, u8, 0, 100>;
+///
+/// assert_eq!(Percent::try_new(42).map(|p| p.get()), Some(42));
+/// assert_eq!(Percent::try_new(101), None);
+/// ```
+///
+/// Invalid bounds are rejected at compile time, `MIN` must not exceed
+/// `MAX` and both must be representable by `Repr`:
+///
+/// ```compile_fail
+/// use phantom_newtype::Bounded;
+///
+/// enum PercentUnit {}
+/// type Percent = Bounded<PercentUnit, u8, 0, 300>;
+///
+/// let _ = Percent::try_new(42);
+/// ```
+///
+/// ```compile_fail
+/// use phantom_newtype::Bounded;
+///
+/// enum PercentUnit {}
+/// type Percent = Bounded<PercentUnit, u8, 100, 0>;
+///
+/// let _ = Percent::try_new(42);
+/// ```
+///
+/// Bounded amounts with different units or bounds are different types:
+///
+/// ```compile_fail
+/// use phantom_newtype::Bounded;
+///
+/// enum PortUnit {}
+/// type Port = Bounded<PortUnit, u16, 1, 65535>;
+/// type UserPort = Bounded<PortUnit, u16, 1024, 49151>;
+///
+/// assert_eq!(Port::try_new(8080), UserPort::try_new(8080));
+/// ```
+///
+/// Arithmetic either reports leaving the range or saturates at the
+/// bounds:
+///
+/// ```
+/// use phantom_newtype::Bounded;
+///
+/// enum RetryUnit {}
+/// type Retries = Bounded<RetryUnit, u32, 0, 5>;
+///
+/// let three = Retries::try_new(3).unwrap();
+///
+/// assert_eq!(three.checked_add(three), None);
+/// assert_eq!(three.checked_sub(three), Retries::try_new(0));
+/// assert_eq!(three.saturating_add(three), Retries::max_value());
+/// assert_eq!(three.saturating_sub(three).saturating_sub(three), Retries::min_value());
+/// assert_eq!(three.saturating_mul(2), Retries::max_value());
+/// ```
+///
+/// Deserialization fails if the value is out of range:
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Bounded;
+/// use serde_json;
+///
+/// enum PercentUnit {}
+/// type Percent = Bounded<PercentUnit, u8, 0, 100>;
+///
+/// assert_eq!(serde_json::from_str::<Percent>("50").unwrap(), Percent::try_new(50).unwrap());
+/// assert!(serde_json::from_str::<Percent>("150").is_err());
+/// assert_eq!(serde_json::to_string(&Percent::try_new(50).unwrap()).unwrap(), "50");
+/// }
+/// ```
+pub struct Bounded<Unit, Repr, const MIN: i128, const MAX: i128>(
+    Repr,
+    PhantomData<std::sync::Mutex<Unit>>,
+);
+
+/// Representations of [`Bounded`](struct.Bounded.html) amounts,
+/// implemented by all primitive integer types.
+pub trait BoundedRepr: TryInto<i128> + Copy + Sealed {
+    /// The smallest value of the type.
+    const MIN: i128;
+
+    /// The largest value of the type, saturated to `i128::MAX`.
+    const MAX: i128;
+
+    /// Converts a value in `[Self::MIN, Self::MAX]` to `i128`.
+    fn to_i128(self) -> i128;
+
+    /// Converts an `i128` in `[Self::MIN, Self::MAX]` to the type.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_bounded_repr {
+    ($($repr:ident),*) => {
+        $(
+            impl BoundedRepr for $repr {
+                const MIN: i128 = $repr::MIN as i128;
+                const MAX: i128 = if $repr::MAX as u128 > i128::MAX as u128 {
+                    i128::MAX
+                } else {
+                    $repr::MAX as i128
+                };
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $repr
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_repr!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> Bounded<Unit, Repr, MIN, MAX> {
+    /// Returns the wrapped value.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Unit, Repr: BoundedRepr, const MIN: i128, const MAX: i128> Bounded<Unit, Repr, MIN, MAX> {
+    // Evaluating this constant fails the compilation if the bounds
+    // are invalid for the `Repr`. All constructors refer to it.
+    const VALID_BOUNDS: () = {
+        assert!(MIN <= MAX, "MIN must not exceed MAX");
+        assert!(Repr::MIN <= MIN, "MIN must be representable by Repr");
+        assert!(MAX <= Repr::MAX, "MAX must be representable by Repr");
+    };
+
+    /// Constructs a new bounded amount, returns `None` if the value
+    /// is out of range.
+    pub fn try_new(repr: Repr) -> Option<Self> {
+        Self::from_i128(repr.to_i128())
+    }
+
+    /// Returns the smallest value of this type.
+    pub fn min_value() -> Self {
+        Self::new_unchecked(MIN)
+    }
+
+    /// Returns the largest value of this type.
+    pub fn max_value() -> Self {
+        Self::new_unchecked(MAX)
+    }
+
+    /// Adds two bounded amounts, returns `None` if the result is out
+    /// of range.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.to_i128()
+            .checked_add(rhs.to_i128())
+            .and_then(Self::from_i128)
+    }
+
+    /// Subtracts two bounded amounts, returns `None` if the result is
+    /// out of range.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.to_i128()
+            .checked_sub(rhs.to_i128())
+            .and_then(Self::from_i128)
+    }
+
+    /// Scales a bounded amount, returns `None` if the result is out of
+    /// range.
+    pub fn checked_mul(self, rhs: Repr) -> Option<Self> {
+        match rhs.try_into() {
+            Ok(rhs) => self.to_i128().checked_mul(rhs).and_then(Self::from_i128),
+            // `rhs` is a huge positive number that doesn't fit into `i128`,
+            // only zero can be scaled by it.
+            Err(_) if self.to_i128() == 0 => Some(self),
+            Err(_) => None,
+        }
+    }
+
+    /// Adds two bounded amounts, clamping the result to the range.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self::clamp(self.to_i128().saturating_add(rhs.to_i128()))
+    }
+
+    /// Subtracts two bounded amounts, clamping the result to the
+    /// range.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self::clamp(self.to_i128().saturating_sub(rhs.to_i128()))
+    }
+
+    /// Scales a bounded amount, clamping the result to the range.
+    pub fn saturating_mul(self, rhs: Repr) -> Self {
+        match rhs.try_into() {
+            Ok(rhs) => Self::clamp(self.to_i128().saturating_mul(rhs)),
+            // `rhs` is a huge positive number that doesn't fit into `i128`.
+            Err(_) => match self.to_i128().cmp(&0) {
+                Ordering::Less => Self::min_value(),
+                Ordering::Equal => self,
+                Ordering::Greater => Self::max_value(),
+            },
+        }
+    }
+
+    fn to_i128(self) -> i128 {
+        self.0.to_i128()
+    }
+
+    fn from_i128(value: i128) -> Option<Self> {
+        if MIN <= value && value <= MAX {
+            Some(Self::new_unchecked(value))
+        } else {
+            None
+        }
+    }
+
+    fn clamp(value: i128) -> Self {
+        Self::new_unchecked(value.clamp(MIN, MAX))
+    }
+
+    // `value` must lie in `[MIN, MAX]`.
+    fn new_unchecked(value: i128) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_BOUNDS;
+        Bounded(Repr::from_i128(value), PhantomData)
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> From<Bounded<Unit, Repr, MIN, MAX>>
+    for Amount<Unit, Repr>
+{
+    fn from(bounded: Bounded<Unit, Repr, MIN, MAX>) -> Self {
+        Amount::new(bounded.0)
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> Clone for Bounded<Unit, Repr, MIN, MAX> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> Copy for Bounded<Unit, Repr, MIN, MAX> {}
+
+impl<Unit, Repr: PartialEq, const MIN: i128, const MAX: i128> PartialEq
+    for Bounded<Unit, Repr, MIN, MAX>
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Eq, const MIN: i128, const MAX: i128> Eq for Bounded<Unit, Repr, MIN, MAX> {}
+
+impl<Unit, Repr: PartialOrd, const MIN: i128, const MAX: i128> PartialOrd
+    for Bounded<Unit, Repr, MIN, MAX>
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Ord, const MIN: i128, const MAX: i128> Ord for Bounded<Unit, Repr, MIN, MAX> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Hash, const MIN: i128, const MAX: i128> Hash for Bounded<Unit, Repr, MIN, MAX> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Unit, Repr, const MIN: i128, const MAX: i128> fmt::Debug for Bounded<Unit, Repr, MIN, MAX>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Unit, Repr, const MIN: i128, const MAX: i128> fmt::Display for Bounded<Unit, Repr, MIN, MAX>
+where
+    Repr: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Unit, Repr: Serialize, const MIN: i128, const MAX: i128> Serialize
+    for Bounded<Unit, Repr, MIN, MAX>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+// Unlike other archetypes, bounded amounts have to validate the
+// deserialized value.
+#[cfg(feature = "serde")]
+impl<'de, Unit, Repr, const MIN: i128, const MAX: i128> Deserialize<'de>
+    for Bounded<Unit, Repr, MIN, MAX>
+where
+    Repr: Deserialize<'de> + BoundedRepr,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        Self::try_new(repr).ok_or_else(|| {
+            D::Error::custom(format_args!("value is out of range [{}, {}]", MIN, MAX))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Test {}
+
+    #[test]
+    fn test_saturation_at_repr_edges() {
+        type Full = Bounded<Test, u8, 0, 255>;
+        let max = Full::max_value();
+        let min = Full::min_value();
+        assert_eq!(max.get(), u8::MAX);
+        assert_eq!(max.saturating_add(max), max);
+        assert_eq!(min.saturating_sub(max), min);
+        assert_eq!(max.saturating_mul(u8::MAX), max);
+        assert_eq!(max.checked_add(Full::try_new(1).unwrap()), None);
+
+        type Signed = Bounded<Test, i8, -128, 127>;
+        let max = Signed::max_value();
+        let min = Signed::min_value();
+        assert_eq!(min.get(), i8::MIN);
+        assert_eq!(min.saturating_sub(max), min);
+        assert_eq!(max.saturating_sub(min), max);
+        assert_eq!(min.saturating_mul(-1), max);
+        assert_eq!(min.checked_mul(-1), None);
+    }
+
+    #[test]
+    fn test_saturation_at_i128_edges() {
+        type Wide = Bounded<Test, i128, { i128::MIN }, { i128::MAX }>;
+        let max = Wide::max_value();
+        let min = Wide::min_value();
+        assert_eq!(max.saturating_add(max), max);
+        assert_eq!(min.saturating_add(min), min);
+        assert_eq!(min.saturating_mul(2), min);
+
+        type Unsigned = Bounded<Test, u128, 0, { i128::MAX }>;
+        let one = Unsigned::try_new(1).unwrap();
+        assert_eq!(Unsigned::try_new(u128::MAX), None);
+        assert_eq!(one.saturating_mul(u128::MAX), Unsigned::max_value());
+        assert_eq!(one.checked_mul(u128::MAX), None);
+        assert_eq!(Unsigned::min_value().saturating_mul(u128::MAX).get(), 0);
+        assert_eq!(
+            Unsigned::min_value().checked_mul(u128::MAX),
+            Some(Unsigned::min_value())
+        );
+    }
+}
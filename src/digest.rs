@@ -0,0 +1,321 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+
+/// A hash function producing `N`-byte [`Digest`](struct.Digest.html)s.
+/// Implementing this trait for an algorithm marker enables
+/// `Digest::of`.
+pub trait HashAlgorithm<const N: usize> {
+    /// Computes the digest of `bytes`.
+    fn hash(bytes: &[u8]) -> [u8; N];
+}
+
+/// `Digest<Algorithm, N>` is an `N`-byte hash computed with the
+/// `Algorithm`. Digests are displayed and parsed as lowercase hex:
+///
+/// ```
+/// use phantom_newtype::Digest;
+///
+/// enum Sha256 {}
+/// type BlobHash = Digest<Sha256, 32>;
+///
+/// let hex = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+/// let hash: BlobHash = hex.parse().unwrap();
+///
+/// assert_eq!(hash.to_string(), hex);
+/// assert_eq!(format!("{:?}", hash), hex);
+/// assert_eq!(hash.as_ref()[0], 0x9f);
+/// assert!("9f86".parse::<BlobHash>().is_err());
+/// ```
+///
+/// Digests are compared for equality in constant time using
+/// [`subtle::ConstantTimeEq`](https://docs.rs/subtle): the comparison
+/// takes the same time wherever the digests differ. Ordering is not
+/// constant-time. Digests of different algorithms can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::Digest;
+///
+/// enum Sha256 {}
+/// enum Blake2s {}
+///
+/// assert_eq!(Digest::<Sha256, 32>::new([0; 32]), Digest::<Blake2s, 32>::new([0; 32]));
+/// ```
+///
+/// Digests can be computed directly if the algorithm implements
+/// [`HashAlgorithm`](trait.HashAlgorithm.html):
+///
+/// ```
+/// use phantom_newtype::{Digest, HashAlgorithm};
+///
+/// enum Xor {}
+///
+/// impl HashAlgorithm<1> for Xor {
+///     fn hash(bytes: &[u8]) -> [u8; 1] {
+///         [bytes.iter().fold(0, |acc, b| acc ^ b)]
+///     }
+/// }
+///
+/// assert_eq!(Digest::<Xor, 1>::of(&[0x0f, 0xf1]).to_string(), "fe");
+/// ```
+///
+/// Digests are serialized as hex strings in human-readable formats and
+/// as bytes otherwise.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Digest;
+/// use serde_json;
+///
+/// enum Md5 {}
+/// type Checksum = Digest<Md5, 16>;
+///
+/// let json = r#""d41d8cd98f00b204e9800998ecf8427e""#;
+/// let checksum: Checksum = serde_json::from_str(json).unwrap();
+/// assert_eq!(serde_json::to_string(&checksum).unwrap(), json);
+/// }
+/// ```
+pub struct Digest<Algorithm, const N: usize>([u8; N], PhantomData<std::sync::Mutex<Algorithm>>);
+
+impl<Algorithm, const N: usize> Digest<Algorithm, N> {
+    /// Constructs a digest from its bytes. This function can be
+    /// evaluated in compile time.
+    pub const fn new(bytes: [u8; N]) -> Digest<Algorithm, N> {
+        Digest(bytes, PhantomData)
+    }
+
+    /// Returns the bytes of the digest.
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Converts the digest into its bytes.
+    pub const fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<Algorithm: HashAlgorithm<N>, const N: usize> Digest<Algorithm, N> {
+    /// Computes the digest of `bytes`.
+    pub fn of(bytes: &[u8]) -> Self {
+        Self::new(Algorithm::hash(bytes))
+    }
+}
+
+/// An error returned when parsing a [`Digest`](struct.Digest.html)
+/// fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseDigestError {
+    /// The input doesn't have exactly two hex digits per byte.
+    InvalidLength,
+    /// The input contains a character that isn't a hex digit.
+    InvalidCharacter,
+}
+
+impl fmt::Display for ParseDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseDigestError::InvalidLength => "invalid digest length",
+            ParseDigestError::InvalidCharacter => "invalid hex character",
+        })
+    }
+}
+
+impl std::error::Error for ParseDigestError {}
+
+impl<Algorithm, const N: usize> FromStr for Digest<Algorithm, N> {
+    type Err = ParseDigestError;
+
+    fn from_str(s: &str) -> Result<Self, ParseDigestError> {
+        if s.len() != 2 * N {
+            return Err(ParseDigestError::InvalidLength);
+        }
+        let nibble = |c: u8| {
+            (c as char)
+                .to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ParseDigestError::InvalidCharacter)
+        };
+        let mut bytes = [0u8; N];
+        for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+            *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+        }
+        Ok(Self::new(bytes))
+    }
+}
+
+impl<Algorithm, const N: usize> From<[u8; N]> for Digest<Algorithm, N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<Algorithm, const N: usize> AsRef<[u8]> for Digest<Algorithm, N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<Algorithm, const N: usize> Clone for Digest<Algorithm, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Algorithm, const N: usize> Copy for Digest<Algorithm, N> {}
+
+impl<Algorithm, const N: usize> PartialEq for Digest<Algorithm, N> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.ct_eq(&rhs.0).into()
+    }
+}
+
+impl<Algorithm, const N: usize> Eq for Digest<Algorithm, N> {}
+
+impl<Algorithm, const N: usize> PartialOrd for Digest<Algorithm, N> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+/// Compares digests lexicographically. Unlike equality, the comparison
+/// stops at the first mismatching byte and is not constant-time.
+impl<Algorithm, const N: usize> Ord for Digest<Algorithm, N> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Algorithm, const N: usize> Hash for Digest<Algorithm, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Algorithm, const N: usize> fmt::Debug for Digest<Algorithm, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<Algorithm, const N: usize> fmt::Display for Digest<Algorithm, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Algorithm, const N: usize> Serialize for Digest<Algorithm, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Algorithm, const N: usize> Deserialize<'de> for Digest<Algorithm, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DigestVisitor<Algorithm, const N: usize>(PhantomData<fn() -> Digest<Algorithm, N>>);
+
+        impl<'de, Algorithm, const N: usize> Visitor<'de> for DigestVisitor<Algorithm, N> {
+            type Value = Digest<Algorithm, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} bytes or a string of {} hex digits", N, 2 * N)
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                if v.len() != N {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                let mut bytes = [0u8; N];
+                bytes.copy_from_slice(v);
+                Ok(Digest::new(bytes))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = [0u8; N];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(A::Error::invalid_length(N + 1, &self));
+                }
+                Ok(Digest::new(bytes))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DigestVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(DigestVisitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Test {}
+    type TestDigest = Digest<Test, 2>;
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(TestDigest::new([1, 2]), TestDigest::new([1, 2]));
+        assert_ne!(TestDigest::new([1, 2]), TestDigest::new([1, 3]));
+        assert_ne!(TestDigest::new([1, 2]), TestDigest::new([0, 2]));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("0aFf".parse(), Ok(TestDigest::new([0x0a, 0xff])));
+        assert_eq!(
+            "0aff0".parse::<TestDigest>(),
+            Err(ParseDigestError::InvalidLength)
+        );
+        assert_eq!(
+            "0afg".parse::<TestDigest>(),
+            Err(ParseDigestError::InvalidCharacter)
+        );
+        assert_eq!(
+            "+0ff".parse::<TestDigest>(),
+            Err(ParseDigestError::InvalidCharacter)
+        );
+        assert_eq!(
+            "é0a".parse::<TestDigest>(),
+            Err(ParseDigestError::InvalidCharacter)
+        );
+    }
+}
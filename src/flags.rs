@@ -0,0 +1,481 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::sealed::Sealed;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+/// Representations of [`Flags`](struct.Flags.html), implemented by
+/// unsigned integer types. This trait is sealed and can't be
+/// implemented outside of this crate.
+pub trait BitsRepr:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+    + TryFrom<u128>
+    + fmt::LowerHex
+    + 'static
+    + Sealed
+{
+    /// The value with no bits set.
+    const EMPTY: Self;
+}
+
+macro_rules! impl_bits_repr {
+    ($($repr:ident),*) => {
+        $(
+            impl BitsRepr for $repr {
+                const EMPTY: Self = 0;
+            }
+        )*
+    };
+}
+
+impl_bits_repr!(u8, u16, u32, u64, u128, usize);
+
+/// Declares the names of the bits of [`Flags`](struct.Flags.html)
+/// with the `Repr` representation. A flag can span several bits.
+pub trait NamedFlags<Repr: BitsRepr> {
+    /// Names of the flags and their bits.
+    const FLAGS: &'static [(&'static str, Repr)];
+}
+
+/// `Flags<Marker, Repr>` is a set of bit flags with names declared by
+/// the `Marker` (see [`NamedFlags`](trait.NamedFlags.html)).
+///
+/// ```
+/// use phantom_newtype::{Flags, NamedFlags};
+///
+/// enum FilePermission {}
+///
+/// impl NamedFlags<u32> for FilePermission {
+///     const FLAGS: &'static [(&'static str, u32)] =
+///         &[("READ", 0b001), ("WRITE", 0b010), ("EXEC", 0b100)];
+/// }
+///
+/// type Permissions = Flags<FilePermission, u32>;
+///
+/// const READ: Permissions = Permissions::new(0b001);
+/// const WRITE: Permissions = Permissions::new(0b010);
+/// const EXEC: Permissions = Permissions::new(0b100);
+///
+/// let rw = READ | WRITE;
+///
+/// assert!(rw.contains(READ));
+/// assert!(!rw.contains(READ | EXEC));
+/// assert_eq!(rw & WRITE, WRITE);
+/// assert_eq!(!rw, EXEC);
+/// assert_eq!(rw.iter().collect::<Vec<_>>(), vec![READ, WRITE]);
+/// assert_eq!(rw.to_string(), "READ | WRITE");
+/// ```
+///
+/// Flags with different markers can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::Flags;
+///
+/// enum FilePermission {}
+/// enum SocketOption {}
+///
+/// let mixed = Flags::<FilePermission, u32>::new(1) | Flags::<SocketOption, u32>::new(1);
+/// ```
+///
+/// Flags are serialized as a list of names in human-readable formats
+/// and as an integer otherwise. Bits that have no name are kept as a
+/// hexadecimal number at the end of the list, like in `Display`.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{Flags, NamedFlags};
+/// use serde_json;
+///
+/// enum FilePermission {}
+///
+/// impl NamedFlags<u8> for FilePermission {
+///     const FLAGS: &'static [(&'static str, u8)] = &[("READ", 1), ("WRITE", 2)];
+/// }
+///
+/// type Permissions = Flags<FilePermission, u8>;
+///
+/// let rw = Permissions::new(3);
+/// assert_eq!(serde_json::to_string(&rw).unwrap(), r#"["READ","WRITE"]"#);
+/// assert_eq!(serde_json::to_string(&Permissions::new(5)).unwrap(), r#"["READ","0x4"]"#);
+/// assert_eq!(serde_json::to_string(&Permissions::empty()).unwrap(), "[]");
+/// assert_eq!(serde_json::from_str::<Permissions>(r#"["WRITE","READ"]"#).unwrap(), rw);
+/// assert_eq!(serde_json::from_str::<Permissions>(r#"["READ","0x4"]"#).unwrap(), Permissions::new(5));
+/// assert!(serde_json::from_str::<Permissions>(r#"["DELETE"]"#).is_err());
+/// assert!(serde_json::from_str::<Permissions>("3").is_err());
+/// }
+/// ```
+pub struct Flags<Marker, Repr>(Repr, PhantomData<std::sync::Mutex<Marker>>);
+
+impl<Marker, Repr> Flags<Marker, Repr> {
+    /// Constructs flags from raw bits, keeping the bits that have no
+    /// name. This function can be evaluated in compile time.
+    pub const fn new(bits: Repr) -> Flags<Marker, Repr> {
+        Flags(bits, PhantomData)
+    }
+}
+
+impl<Marker, Repr: Copy> Flags<Marker, Repr> {
+    /// Returns the raw bits.
+    pub fn bits(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Marker, Repr: BitsRepr> Flags<Marker, Repr> {
+    /// Returns flags with no bits set.
+    pub fn empty() -> Self {
+        Self::new(Repr::EMPTY)
+    }
+
+    /// Checks whether no bits are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == Repr::EMPTY
+    }
+
+    /// Checks whether all the bits of `other` are set in `self`.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Checks whether `self` and `other` have bits in common.
+    pub fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != Repr::EMPTY
+    }
+
+    /// Sets the bits of `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 = self.0 | other.0
+    }
+
+    /// Clears the bits of `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 = self.0 & !other.0
+    }
+}
+
+impl<Marker, Repr> Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr,
+{
+    /// Returns flags with all the named bits set.
+    pub fn all() -> Self {
+        Self::new(
+            Marker::FLAGS
+                .iter()
+                .fold(Repr::EMPTY, |acc, (_, bits)| acc | *bits),
+        )
+    }
+
+    /// Constructs flags from raw bits, returns `None` if some of the
+    /// bits have no name.
+    pub fn from_bits(bits: Repr) -> Option<Self> {
+        let flags = Self::new(bits);
+        if Self::all().contains(flags) {
+            Some(flags)
+        } else {
+            None
+        }
+    }
+
+    /// Constructs flags from raw bits, dropping the bits that have no
+    /// name.
+    pub fn from_bits_truncate(bits: Repr) -> Self {
+        Self::new(bits) & Self::all()
+    }
+
+    /// Returns the flag with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Marker::FLAGS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, bits)| Self::new(*bits))
+    }
+
+    /// Iterates over the named flags set in `self`, in the order of
+    /// declaration. Flags whose bits were already yielded as a part of
+    /// other flags are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = Self> {
+        self.iter_names().map(|(_, flag)| flag)
+    }
+
+    /// Same as `iter`, but also yields the names of the flags.
+    pub fn iter_names(&self) -> impl Iterator<Item = (&'static str, Self)> {
+        let bits = self.0;
+        let mut remaining = self.0;
+        Marker::FLAGS.iter().filter_map(move |(name, flag)| {
+            let flag = *flag;
+            if flag != Repr::EMPTY && bits & flag == flag && remaining & flag != Repr::EMPTY {
+                remaining = remaining & !flag;
+                Some((*name, Self::new(flag)))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the bits that have no name.
+    fn unnamed_bits(&self) -> Repr {
+        self.0 & !Self::all().0
+    }
+}
+
+impl<Marker, Repr: BitsRepr> Default for Flags<Marker, Repr> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<Marker, Repr: Copy> From<Repr> for Flags<Marker, Repr> {
+    fn from(bits: Repr) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl<Marker, Repr: BitsRepr> FromIterator<Self> for Flags<Marker, Repr> {
+    fn from_iter<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::empty(), |acc, flag| acc | flag)
+    }
+}
+
+impl<Marker, Repr: Copy> Clone for Flags<Marker, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Marker, Repr: Copy> Copy for Flags<Marker, Repr> {}
+
+impl<Marker, Repr: PartialEq> PartialEq for Flags<Marker, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Marker, Repr: Eq> Eq for Flags<Marker, Repr> {}
+
+impl<Marker, Repr: PartialOrd> PartialOrd for Flags<Marker, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Marker, Repr: Ord> Ord for Flags<Marker, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Marker, Repr: Hash> Hash for Flags<Marker, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Marker, Repr: BitsRepr> BitOr for Flags<Marker, Repr> {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self {
+        self.bitor_assign(rhs);
+        self
+    }
+}
+
+impl<Marker, Repr: BitsRepr> BitOrAssign for Flags<Marker, Repr> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 = self.0 | rhs.0
+    }
+}
+
+impl<Marker, Repr: BitsRepr> BitAnd for Flags<Marker, Repr> {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self {
+        self.bitand_assign(rhs);
+        self
+    }
+}
+
+impl<Marker, Repr: BitsRepr> BitAndAssign for Flags<Marker, Repr> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 = self.0 & rhs.0
+    }
+}
+
+/// Complements the named bits, bits without a name are cleared.
+impl<Marker, Repr> Not for Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::from_bits_truncate(!self.0)
+    }
+}
+
+impl<Marker, Repr> fmt::Debug for Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Lists the names of the flags separated by " | ". Bits without a
+/// name are printed as a hexadecimal number.
+impl<Marker, Repr> fmt::Display for Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for (name, _) in self.iter_names() {
+            write!(f, "{}{}", separator, name)?;
+            separator = " | ";
+        }
+        let unnamed = self.unnamed_bits();
+        if unnamed != Repr::EMPTY {
+            write!(f, "{}{:#x}", separator, unnamed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Marker, Repr> Serialize for Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return self.0.serialize(serializer);
+        }
+        let mut seq = serializer.serialize_seq(None)?;
+        for (name, _) in self.iter_names() {
+            seq.serialize_element(name)?;
+        }
+        let unnamed = self.unnamed_bits();
+        if unnamed != Repr::EMPTY {
+            seq.serialize_element(&format!("{:#x}", unnamed))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Marker, Repr> Deserialize<'de> for Flags<Marker, Repr>
+where
+    Marker: NamedFlags<Repr>,
+    Repr: BitsRepr + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FlagsVisitor<Marker, Repr>(PhantomData<fn() -> Flags<Marker, Repr>>);
+
+        impl<'de, Marker, Repr> Visitor<'de> for FlagsVisitor<Marker, Repr>
+        where
+            Marker: NamedFlags<Repr>,
+            Repr: BitsRepr,
+        {
+            type Value = Flags<Marker, Repr>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a list of flag names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut flags = Flags::empty();
+                while let Some(name) = seq.next_element::<String>()? {
+                    let flag = match name.strip_prefix("0x") {
+                        Some(hex) => u128::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(|bits| Repr::try_from(bits).ok())
+                            .map(Flags::new),
+                        None => Flags::from_name(&name),
+                    };
+                    flags |= flag
+                        .ok_or_else(|| A::Error::custom(format_args!("unknown flag {:?}", name)))?;
+                }
+                Ok(flags)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_seq(FlagsVisitor(PhantomData))
+        } else {
+            Repr::deserialize(deserializer).map(Flags::new)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Mode {}
+
+    impl NamedFlags<u8> for Mode {
+        const FLAGS: &'static [(&'static str, u8)] = &[
+            ("READ", 0b001),
+            ("WRITE", 0b010),
+            ("READ_WRITE", 0b011),
+            ("EXEC", 0b100),
+        ];
+    }
+
+    type Modes = Flags<Mode, u8>;
+
+    #[test]
+    fn test_composite_flags() {
+        let rw = Modes::new(0b011);
+
+        assert_eq!(
+            rw.iter().collect::<Vec<_>>(),
+            vec![Modes::new(0b001), Modes::new(0b010)]
+        );
+        assert_eq!(Modes::from_name("READ_WRITE"), Some(rw));
+        assert_eq!(Modes::all(), Modes::new(0b111));
+    }
+
+    #[test]
+    fn test_unnamed_bits() {
+        assert_eq!(Modes::from_bits(0b1001), None);
+        assert_eq!(Modes::from_bits_truncate(0b1001), Modes::new(0b001));
+        assert_eq!(Modes::new(0b1001).to_string(), "READ | 0x8");
+        assert_eq!(Modes::new(0b1000).to_string(), "0x8");
+        assert_eq!(Modes::empty().to_string(), "");
+        assert_eq!(!Modes::new(0b1001), Modes::new(0b110));
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::rounding::{div_round, from_i128, to_i128, to_sign_magnitude, Rounding};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// Describes how many parts make up a whole for a
+/// [`Fraction`](struct.Fraction.html).
+pub trait FractionScale {
+    /// The number of parts in one whole, e.g. 100 for percents.
+    const DENOMINATOR: u32;
+}
+
+/// Fractions expressed in hundredths.
+pub enum Percent {}
+
+/// Fractions expressed in ten-thousandths.
+pub enum BasisPoints {}
+
+/// Fractions expressed in millionths.
+pub enum PartsPerMillion {}
+
+impl FractionScale for Percent {
+    const DENOMINATOR: u32 = 100;
+}
+
+impl FractionScale for BasisPoints {
+    const DENOMINATOR: u32 = 10_000;
+}
+
+impl FractionScale for PartsPerMillion {
+    const DENOMINATOR: u32 = 1_000_000;
+}
+
+/// `Fraction<Scale, Repr>` is a fraction of a whole stored as a number
+/// of parts, the number of parts in a whole is determined by `Scale`
+/// (see [`FractionScale`](trait.FractionScale.html)).
+///
+/// Fractions are applied to amounts with an explicit rounding mode:
+///
+/// ```
+/// use phantom_newtype::{Amount, BasisPoints, Fraction, Rounding};
+///
+/// enum Cents {}
+/// type Money = Amount<Cents, u64>;
+/// type Fee = Fraction<BasisPoints, u32>;
+///
+/// let fee = Fee::new(125); // 1.25%
+///
+/// assert_eq!(Money::from(1000).mul_fraction(fee, Rounding::Floor), Money::from(12));
+/// assert_eq!(Money::from(1000).mul_fraction(fee, Rounding::Ceil), Money::from(13));
+/// assert_eq!(Money::from(1000).mul_fraction(fee, Rounding::HalfEven), Money::from(12));
+/// ```
+///
+/// Fractions can be composed and complemented:
+///
+/// ```
+/// use phantom_newtype::{Fraction, Percent, Rounding};
+///
+/// type Pct = Fraction<Percent, u8>;
+///
+/// let discount = Pct::new(20);
+/// assert_eq!(discount.complement(), Pct::new(80));
+/// assert_eq!(discount.compose(Pct::new(50), Rounding::Floor), Some(Pct::new(10)));
+/// assert_eq!(Pct::one(), Pct::new(100));
+/// ```
+///
+/// Fractions with different scales can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::{BasisPoints, Fraction, Percent};
+///
+/// assert_eq!(Fraction::<Percent, u32>::new(1), Fraction::<BasisPoints, u32>::new(100));
+/// ```
+///
+/// Fractions are displayed as percents:
+///
+/// ```
+/// use phantom_newtype::{BasisPoints, Fraction, PartsPerMillion, Percent};
+///
+/// assert_eq!(Fraction::<BasisPoints, u32>::new(1250).to_string(), "12.5%");
+/// assert_eq!(Fraction::<PartsPerMillion, i32>::new(-5).to_string(), "-0.0005%");
+/// assert_eq!(Fraction::<Percent, u8>::new(3).to_string(), "3%");
+/// ```
+///
+/// Fractions are serialized as the number of parts.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{BasisPoints, Fraction};
+/// use serde_json;
+///
+/// type Fee = Fraction<BasisPoints, u32>;
+///
+/// assert_eq!(serde_json::to_string(&Fee::new(125)).unwrap(), "125");
+/// assert_eq!(serde_json::from_str::<Fee>("125").unwrap(), Fee::new(125));
+/// }
+/// ```
+pub struct Fraction<Scale, Repr>(Repr, PhantomData<std::sync::Mutex<Scale>>);
+
+impl<Scale, Repr> Fraction<Scale, Repr> {
+    /// Constructs a fraction from the number of parts. This function
+    /// can be evaluated in compile time.
+    pub const fn new(parts: Repr) -> Fraction<Scale, Repr> {
+        Fraction(parts, PhantomData)
+    }
+}
+
+impl<Scale, Repr: Copy> Fraction<Scale, Repr> {
+    /// Returns the number of parts.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Scale, Repr> Fraction<Scale, Repr>
+where
+    Scale: FractionScale,
+    Repr: TryFrom<i128> + TryInto<i128> + Copy,
+{
+    /// Returns the fraction that makes up a whole.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `Repr` can't hold `Scale::DENOMINATOR`, e.g. parts per
+    /// million in `u8`.
+    pub fn one() -> Self {
+        Self::from_i128(Scale::DENOMINATOR.into())
+    }
+
+    /// Returns `1 - self`. Panics if the result can't be represented,
+    /// e.g. if an unsigned fraction is greater than one.
+    pub fn complement(self) -> Self {
+        Self::from_i128(i128::from(Scale::DENOMINATOR) - to_i128(self.0))
+    }
+
+    /// Returns the fraction of a fraction, `self * other`, rounded
+    /// according to `rounding`. Returns `None` if the result can't be
+    /// represented.
+    ///
+    /// ```
+    /// use phantom_newtype::{Fraction, PartsPerMillion, Rounding};
+    ///
+    /// type Huge = Fraction<PartsPerMillion, u128>;
+    ///
+    /// let x = Huge::new(u128::MAX / 2);
+    /// assert_eq!(x.compose(Huge::one(), Rounding::Floor), None);
+    /// assert_eq!(Huge::new(1_000).compose(Huge::new(1_000), Rounding::Ceil), Some(Huge::new(1)));
+    /// ```
+    pub fn compose(self, other: Self, rounding: Rounding) -> Option<Self> {
+        let lhs: i128 = self.0.try_into().ok()?;
+        let rhs: i128 = other.0.try_into().ok()?;
+        let parts = div_round(lhs.checked_mul(rhs)?, Scale::DENOMINATOR.into(), rounding);
+        Repr::try_from(parts).ok().map(Self::new)
+    }
+
+    fn from_i128(parts: i128) -> Self {
+        Self::new(from_i128(parts))
+    }
+}
+
+impl<Unit, Repr> Amount<Unit, Repr>
+where
+    Repr: TryFrom<i128> + TryInto<i128> + Copy,
+{
+    /// Multiplies the amount by a fraction rounding the result
+    /// according to `rounding`. Panics if the result doesn't fit into
+    /// `Repr`.
+    pub fn mul_fraction<Scale, FractionRepr>(
+        self,
+        fraction: Fraction<Scale, FractionRepr>,
+        rounding: Rounding,
+    ) -> Self
+    where
+        Scale: FractionScale,
+        FractionRepr: TryInto<i128> + Copy,
+    {
+        let product = to_i128(self.get())
+            .checked_mul(to_i128(fraction.0))
+            .expect("attempt to multiply with overflow");
+        Amount::new(from_i128(div_round(
+            product,
+            Scale::DENOMINATOR.into(),
+            rounding,
+        )))
+    }
+}
+
+impl<Scale, Repr: Copy> From<Repr> for Fraction<Scale, Repr> {
+    fn from(parts: Repr) -> Self {
+        Self::new(parts)
+    }
+}
+
+impl<Scale, Repr: Copy> Clone for Fraction<Scale, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Scale, Repr: Copy> Copy for Fraction<Scale, Repr> {}
+
+impl<Scale, Repr: PartialEq> PartialEq for Fraction<Scale, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Scale, Repr: Eq> Eq for Fraction<Scale, Repr> {}
+
+impl<Scale, Repr: PartialOrd> PartialOrd for Fraction<Scale, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Scale, Repr: Ord> Ord for Fraction<Scale, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Scale, Repr: Hash> Hash for Fraction<Scale, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Scale, Repr> fmt::Debug for Fraction<Scale, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Scale, Repr> fmt::Display for Fraction<Scale, Repr>
+where
+    Scale: FractionScale,
+    Repr: TryInto<i128> + TryInto<u128> + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (negative, parts) = to_sign_magnitude(self.0);
+        let denominator = u128::from(Scale::DENOMINATOR);
+        if negative {
+            f.write_str("-")?;
+        }
+        // Percents are the decimal expansion of `parts / denominator`
+        // with the point moved by two digits. Expanding digit by digit
+        // keeps the intermediate values below `10 * denominator`.
+        let next_digit = |remainder: &mut u128| {
+            *remainder *= 10;
+            let digit = *remainder / denominator;
+            *remainder %= denominator;
+            digit
+        };
+        let wholes = parts / denominator;
+        let mut remainder = parts % denominator;
+        let hundredths = next_digit(&mut remainder) * 10 + next_digit(&mut remainder);
+        if wholes == 0 {
+            write!(f, "{}", hundredths)?;
+        } else {
+            write!(f, "{}{:02}", wholes, hundredths)?;
+        }
+        if remainder != 0 {
+            f.write_str(".")?;
+        }
+        // Denominators of the predefined scales are powers of ten, so
+        // the expansion is finite. Custom scales are cut off.
+        let mut digits = 0;
+        while remainder != 0 && digits < 12 {
+            write!(f, "{}", next_digit(&mut remainder))?;
+            digits += 1;
+        }
+        f.write_str("%")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Scale, Repr: Serialize> Serialize for Fraction<Scale, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Scale, Repr> Deserialize<'de> for Fraction<Scale, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Fraction::<Scale, Repr>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_at_repr_edges() {
+        assert_eq!(
+            Fraction::<Percent, u128>::new(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455%"
+        );
+        assert_eq!(
+            Fraction::<BasisPoints, u128>::new(u128::MAX).to_string(),
+            "3402823669209384634633746074317682114.55%"
+        );
+        assert_eq!(
+            Fraction::<Percent, i128>::new(i128::MAX).to_string(),
+            "170141183460469231731687303715884105727%"
+        );
+        assert_eq!(
+            Fraction::<PartsPerMillion, i128>::new(i128::MIN).to_string(),
+            "-17014118346046923173168730371588410.5728%"
+        );
+        assert_eq!(Fraction::<Percent, i8>::new(i8::MIN).to_string(), "-128%");
+        assert_eq!(Fraction::<PartsPerMillion, u8>::new(0).to_string(), "0%");
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::retag::Retag;
 #[cfg(feature="serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::Ordering;
@@ -168,6 +169,43 @@ impl<Entity, Repr> Id<Entity, Repr> {
     pub const fn new(repr: Repr) -> Id<Entity, Repr> {
         Id(repr, PhantomData)
     }
+
+    /// Converts the identifier into an identifier of another entity.
+    /// The conversion must be allowed by a
+    /// [`Retag`](trait.Retag.html) implementation.
+    ///
+    /// ```
+    /// use phantom_newtype::{Id, Retag};
+    ///
+    /// enum Draft {}
+    /// enum Post {}
+    /// // Publishing a draft keeps its id.
+    /// impl Retag<Post> for Draft {}
+    ///
+    /// let draft = Id::<Draft, u32>::from(7);
+    /// let post: Id<Post, u32> = draft.retag();
+    /// assert_eq!(post, Id::from(7));
+    /// ```
+    pub fn retag<To>(self) -> Id<To, Repr>
+    where
+        Entity: Retag<To>,
+    {
+        Id::new(self.0)
+    }
+
+    /// Drops the entity and returns the underlying representation.
+    ///
+    /// ```
+    /// use phantom_newtype::Id;
+    ///
+    /// enum User {}
+    ///
+    /// let id = Id::<User, String>::from("alice".to_string());
+    /// assert_eq!(id.forget_tag(), "alice");
+    /// ```
+    pub fn forget_tag(self) -> Repr {
+        self.0
+    }
 }
 
 impl<Entity, Repr> Id<Entity, Repr>
@@ -212,19 +250,19 @@ impl<Entity, Repr: Copy> Copy for Id<Entity, Repr> {}
 
 impl<Entity, Repr: PartialEq> PartialEq for Id<Entity, Repr> {
     fn eq(&self, rhs: &Self) -> bool {
-        self.get().eq(&rhs.get())
+        self.get().eq(rhs.get())
     }
 }
 
 impl<Entity, Repr: PartialOrd> PartialOrd for Id<Entity, Repr> {
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
-        self.get().partial_cmp(&rhs.get())
+        self.get().partial_cmp(rhs.get())
     }
 }
 
 impl<Entity, Repr: Ord> Ord for Id<Entity, Repr> {
     fn cmp(&self, rhs: &Self) -> Ordering {
-        self.get().cmp(&rhs.get())
+        self.get().cmp(rhs.get())
     }
 }
 
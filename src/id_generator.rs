@@ -0,0 +1,309 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::id::Id;
+use crate::sealed::Sealed;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize, Ordering,
+};
+
+/// Representations of [`Id`](struct.Id.html)s that can be produced by
+/// an [`IdGenerator`](struct.IdGenerator.html), implemented by integer
+/// types that have an atomic counterpart. This trait is sealed and
+/// can't be implemented outside of this crate.
+pub trait AtomicRepr: Copy + PartialEq + fmt::Debug + Sealed {
+    /// The atomic counterpart of the type, e.g. `AtomicU64` for `u64`.
+    type Atomic: Send + Sync;
+
+    /// Zero value of the type.
+    const ZERO: Self;
+
+    /// One value of the type.
+    const ONE: Self;
+
+    /// Constructs an atomic holding `value`.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Loads the value of the atomic with relaxed ordering.
+    fn load(atomic: &Self::Atomic) -> Self;
+
+    /// Stores `new` into the atomic if it holds `current`, returns the
+    /// previous value. Like `compare_exchange_weak`, may fail
+    /// spuriously.
+    fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self) -> Result<Self, Self>;
+
+    /// Adds two values, returns `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_atomic_repr {
+    ($($repr:ident($atomic:ident)),*) => {
+        $(
+            impl AtomicRepr for $repr {
+                type Atomic = $atomic;
+
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn new_atomic(value: Self) -> $atomic {
+                    $atomic::new(value)
+                }
+
+                fn load(atomic: &$atomic) -> Self {
+                    atomic.load(Ordering::Relaxed)
+                }
+
+                fn compare_exchange(atomic: &$atomic, current: Self, new: Self) -> Result<Self, Self> {
+                    atomic.compare_exchange_weak(current, new, Ordering::Relaxed, Ordering::Relaxed)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    $repr::checked_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_repr!(
+    u8(AtomicU8),
+    u16(AtomicU16),
+    u32(AtomicU32),
+    u64(AtomicU64),
+    usize(AtomicUsize),
+    i8(AtomicI8),
+    i16(AtomicI16),
+    i32(AtomicI32),
+    i64(AtomicI64),
+    isize(AtomicIsize)
+);
+
+/// `IdGenerator<Entity, Repr>` hands out fresh `Id<Entity, Repr>`
+/// values from an atomic counter. Generators are thread-safe and never
+/// produce the same id twice: once the representation overflows, the
+/// generator is exhausted and returns `None`.
+///
+/// ```
+/// use phantom_newtype::{Id, IdGenerator};
+///
+/// struct User;
+/// type UserId = Id<User, u64>;
+///
+/// let ids = IdGenerator::<User, u64>::new();
+///
+/// assert_eq!(ids.next_id(), Some(UserId::new(0)));
+/// assert_eq!(ids.next_id(), Some(UserId::new(1)));
+/// ```
+///
+/// The start and the step of the generator are configurable:
+///
+/// ```
+/// use phantom_newtype::{Id, IdGenerator};
+///
+/// struct Shard;
+/// type ShardId = Id<Shard, u8>;
+///
+/// let ids = IdGenerator::<Shard, u8>::starting_at(1).with_step(100);
+///
+/// assert_eq!(ids.next_id(), Some(ShardId::new(1)));
+/// assert_eq!(ids.next_id(), Some(ShardId::new(101)));
+/// assert_eq!(ids.next_id(), Some(ShardId::new(201)));
+/// assert_eq!(ids.next_id(), None);
+/// assert!(ids.is_exhausted());
+/// ```
+///
+/// A generator can resume after the last id that was persisted. The
+/// step has to be passed again, it isn't part of the id:
+///
+/// ```
+/// use phantom_newtype::{Id, IdGenerator};
+///
+/// struct User;
+/// type UserId = Id<User, u64>;
+///
+/// let last_persisted = UserId::new(41);
+/// let ids = IdGenerator::resume_after(last_persisted, 1);
+/// assert_eq!(ids.next_id(), Some(UserId::new(42)));
+///
+/// let ids = IdGenerator::resume_after(last_persisted, 10);
+/// assert_eq!(ids.next_id(), Some(UserId::new(51)));
+/// assert_eq!(ids.next_id(), Some(UserId::new(61)));
+///
+/// let ids = IdGenerator::<User, u8>::resume_after(Id::new(250), 10);
+/// assert_eq!(ids.next_id(), None);
+/// ```
+///
+/// Generators can be shared between threads:
+///
+/// ```
+/// use phantom_newtype::IdGenerator;
+/// use std::sync::Arc;
+///
+/// struct Job;
+///
+/// let ids = Arc::new(IdGenerator::<Job, u32>::new());
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let ids = Arc::clone(&ids);
+///         std::thread::spawn(move || ids.next_id().unwrap())
+///     })
+///     .collect();
+///
+/// let mut issued: Vec<_> = handles.into_iter().map(|h| *h.join().unwrap().get()).collect();
+/// issued.sort();
+/// assert_eq!(issued, vec![0, 1, 2, 3]);
+/// ```
+pub struct IdGenerator<Entity, Repr: AtomicRepr> {
+    next: Repr::Atomic,
+    step: Repr,
+    exhausted: AtomicBool,
+    marker: PhantomData<std::sync::Mutex<Entity>>,
+}
+
+impl<Entity, Repr: AtomicRepr> IdGenerator<Entity, Repr> {
+    /// Constructs a generator producing ids `0, 1, 2, ...`.
+    pub fn new() -> Self {
+        Self::starting_at(Repr::ZERO)
+    }
+
+    /// Constructs a generator producing ids starting with `start`
+    /// with step one.
+    pub fn starting_at(start: Repr) -> Self {
+        Self {
+            next: Repr::new_atomic(start),
+            step: Repr::ONE,
+            exhausted: AtomicBool::new(false),
+            marker: PhantomData,
+        }
+    }
+
+    /// Constructs a generator that continues the sequence with the
+    /// given `step` after `last`. The generator is exhausted if the id
+    /// following `last` doesn't fit into `Repr`. Panics if the step is
+    /// zero.
+    pub fn resume_after(last: Id<Entity, Repr>, step: Repr) -> Self {
+        match last.get().checked_add(step) {
+            Some(next) => Self::starting_at(next).with_step(step),
+            None => {
+                let generator = Self::starting_at(*last.get()).with_step(step);
+                generator.exhausted.store(true, Ordering::Relaxed);
+                generator
+            }
+        }
+    }
+
+    /// Changes the difference between consecutive ids. Panics if the
+    /// step is zero.
+    pub fn with_step(mut self, step: Repr) -> Self {
+        assert!(step != Repr::ZERO, "IdGenerator step must not be zero");
+        self.step = step;
+        self
+    }
+
+    /// Returns a fresh id, or `None` if the generator is exhausted.
+    pub fn next_id(&self) -> Option<Id<Entity, Repr>> {
+        // Ids only have to be unique, the generator doesn't
+        // synchronize any other memory, so relaxed ordering is enough.
+        let mut current = Repr::load(&self.next);
+        loop {
+            if self.exhausted.load(Ordering::Relaxed) {
+                return None;
+            }
+            match current.checked_add(self.step) {
+                Some(next) => match Repr::compare_exchange(&self.next, current, next) {
+                    Ok(_) => return Some(Id::new(current)),
+                    Err(actual) => current = actual,
+                },
+                // `current` is the last id we can issue.
+                None => {
+                    return self
+                        .exhausted
+                        .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                        .ok()
+                        .map(|_| Id::new(current))
+                }
+            }
+        }
+    }
+
+    /// Checks whether the generator ran out of ids.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+}
+
+impl<Entity, Repr: AtomicRepr> Default for IdGenerator<Entity, Repr> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Entity, Repr: AtomicRepr> fmt::Debug for IdGenerator<Entity, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdGenerator")
+            .field("next", &Repr::load(&self.next))
+            .field("step", &self.step)
+            .field("exhausted", &self.is_exhausted())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    enum Packet {}
+
+    #[test]
+    fn test_concurrent_generators_exhaust_without_duplicates() {
+        let ids = Arc::new(IdGenerator::<Packet, u8>::starting_at(10).with_step(3));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let ids = Arc::clone(&ids);
+                std::thread::spawn(move || {
+                    let mut issued = Vec::new();
+                    while let Some(id) = ids.next_id() {
+                        issued.push(*id.get());
+                    }
+                    issued
+                })
+            })
+            .collect();
+
+        let mut all = Vec::new();
+        for handle in handles {
+            all.extend(handle.join().unwrap());
+        }
+        let unique: HashSet<_> = all.iter().cloned().collect();
+
+        assert_eq!(all.len(), unique.len());
+        assert_eq!(unique, (10..=u8::MAX).step_by(3).collect());
+        assert!(ids.is_exhausted());
+    }
+
+    #[test]
+    fn test_resume_after_last_id() {
+        let ids = IdGenerator::<Packet, u8>::resume_after(Id::new(u8::MAX), 1);
+        assert_eq!(ids.next_id(), None);
+
+        let ids = IdGenerator::<Packet, u8>::resume_after(Id::new(u8::MAX - 1), 1);
+        assert_eq!(ids.next_id(), Some(Id::new(u8::MAX)));
+        assert_eq!(ids.next_id(), None);
+    }
+}
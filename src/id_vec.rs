@@ -0,0 +1,495 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::id::Id;
+use crate::sealed::Sealed;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, IndexMut, Range};
+
+/// Representations of [`Id`](struct.Id.html)s that can be used as
+/// indices of [`IdVec`](struct.IdVec.html), implemented by unsigned
+/// integer types. This trait is sealed and can't be implemented
+/// outside of this crate.
+pub trait IndexRepr: Copy + Sealed {
+    /// Converts an index into the representation, returns `None` if
+    /// the index doesn't fit.
+    fn try_from_usize(index: usize) -> Option<Self>;
+
+    /// Converts an index into the representation, panics if the index
+    /// doesn't fit.
+    fn from_usize(index: usize) -> Self {
+        Self::try_from_usize(index).expect("index doesn't fit into the Id representation")
+    }
+
+    /// Converts the representation into an index.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_index_repr {
+    ($($repr:ident),*) => {
+        $(
+            impl IndexRepr for $repr {
+                fn try_from_usize(index: usize) -> Option<Self> {
+                    $repr::try_from(index).ok()
+                }
+
+                fn to_usize(self) -> usize {
+                    usize::try_from(self).expect("Id doesn't fit into usize")
+                }
+            }
+        )*
+    };
+}
+
+impl_index_repr!(u8, u16, u32, u64, usize);
+
+// Checks that every element of a `len`-element collection has an id.
+fn can_index<Repr: IndexRepr>(len: usize) -> bool {
+    len == 0 || Repr::try_from_usize(len - 1).is_some()
+}
+
+fn assert_can_index<Repr: IndexRepr>(len: usize) {
+    assert!(
+        can_index::<Repr>(len),
+        "{} elements can't be indexed by the Id representation",
+        len
+    );
+}
+
+/// `IdSlice<Entity, T, Repr>` is a slice of `T` indexed by
+/// `Id<Entity, Repr>` instead of `usize`. It's usually obtained from
+/// an [`IdVec`](struct.IdVec.html).
+///
+/// ```
+/// use phantom_newtype::{Id, IdSlice, IdVec};
+///
+/// struct User;
+/// type UserId = Id<User, u32>;
+///
+/// fn first_name(names: &IdSlice<User, String, u32>) -> &str {
+///     &names[UserId::new(0)]
+/// }
+///
+/// let mut names = IdVec::<User, String, u32>::new();
+/// names.push("alice".to_string());
+/// names.push("bob".to_string());
+///
+/// assert_eq!(first_name(&names), "alice");
+/// ```
+///
+/// Indexing with a range of ids returns a plain slice: its elements
+/// are indexed from zero, so the ids of the parent don't apply to it.
+///
+/// ```
+/// use phantom_newtype::{Id, IdVec};
+///
+/// struct User;
+/// type UserId = Id<User, u32>;
+///
+/// let names: IdVec<User, &str, u32> = vec!["alice", "bob", "carol"].into_iter().collect();
+/// let tail: &[&str] = &names[UserId::new(1)..UserId::new(3)];
+///
+/// assert_eq!(tail, ["bob", "carol"]);
+/// ```
+#[repr(transparent)]
+pub struct IdSlice<Entity, T, Repr = usize> {
+    marker: PhantomData<std::sync::Mutex<(Entity, Repr)>>,
+    raw: [T],
+}
+
+impl<Entity, T, Repr> IdSlice<Entity, T, Repr> {
+    /// Wraps a slice of elements.
+    pub fn from_raw(raw: &[T]) -> &Self {
+        // SAFETY: `IdSlice` is `repr(transparent)` over `[T]`.
+        unsafe { &*(raw as *const [T] as *const Self) }
+    }
+
+    /// Wraps a mutable slice of elements.
+    pub fn from_raw_mut(raw: &mut [T]) -> &mut Self {
+        // SAFETY: `IdSlice` is `repr(transparent)` over `[T]`.
+        unsafe { &mut *(raw as *mut [T] as *mut Self) }
+    }
+
+    /// Returns the underlying slice.
+    pub fn as_raw(&self) -> &[T] {
+        &self.raw
+    }
+
+    /// Returns the underlying mutable slice.
+    pub fn as_raw_mut(&mut self) -> &mut [T] {
+        &mut self.raw
+    }
+
+    /// Returns the number of elements in the slice.
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns `true` if the slice has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Iterates over the elements of the slice.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.raw.iter()
+    }
+
+    /// Iterates over mutable references to the elements of the slice.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.raw.iter_mut()
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> IdSlice<Entity, T, Repr> {
+    /// Returns the element with the specified id, or `None` if the id
+    /// is out of bounds.
+    pub fn get(&self, id: Id<Entity, Repr>) -> Option<&T> {
+        self.raw.get(id.get().to_usize())
+    }
+
+    /// Returns a mutable reference to the element with the specified
+    /// id, or `None` if the id is out of bounds.
+    pub fn get_mut(&mut self, id: Id<Entity, Repr>) -> Option<&mut T> {
+        self.raw.get_mut(id.get().to_usize())
+    }
+
+    /// Iterates over the ids of the elements.
+    pub fn indices(&self) -> impl Iterator<Item = Id<Entity, Repr>> {
+        (0..self.raw.len()).map(|i| Id::new(Repr::from_usize(i)))
+    }
+
+    /// Iterates over the elements together with their ids.
+    ///
+    /// ```
+    /// use phantom_newtype::{Id, IdVec};
+    ///
+    /// struct User;
+    ///
+    /// let names: IdVec<User, &str> = vec!["alice", "bob"].into_iter().collect();
+    /// let pairs: Vec<_> = names.iter_enumerated().collect();
+    ///
+    /// assert_eq!(pairs, vec![(Id::new(0), &"alice"), (Id::new(1), &"bob")]);
+    /// ```
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (Id<Entity, Repr>, &T)> {
+        self.raw
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (Id::new(Repr::from_usize(i)), x))
+    }
+
+    /// Iterates over mutable references to the elements together with
+    /// their ids.
+    pub fn iter_enumerated_mut(&mut self) -> impl Iterator<Item = (Id<Entity, Repr>, &mut T)> {
+        self.raw
+            .iter_mut()
+            .enumerate()
+            .map(|(i, x)| (Id::new(Repr::from_usize(i)), x))
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> Index<Id<Entity, Repr>> for IdSlice<Entity, T, Repr> {
+    type Output = T;
+
+    fn index(&self, id: Id<Entity, Repr>) -> &T {
+        &self.raw[id.get().to_usize()]
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> IndexMut<Id<Entity, Repr>> for IdSlice<Entity, T, Repr> {
+    fn index_mut(&mut self, id: Id<Entity, Repr>) -> &mut T {
+        &mut self.raw[id.get().to_usize()]
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> Index<Range<Id<Entity, Repr>>> for IdSlice<Entity, T, Repr> {
+    type Output = [T];
+
+    fn index(&self, range: Range<Id<Entity, Repr>>) -> &[T] {
+        &self.raw[range.start.get().to_usize()..range.end.get().to_usize()]
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> IndexMut<Range<Id<Entity, Repr>>> for IdSlice<Entity, T, Repr> {
+    fn index_mut(&mut self, range: Range<Id<Entity, Repr>>) -> &mut [T] {
+        &mut self.raw[range.start.get().to_usize()..range.end.get().to_usize()]
+    }
+}
+
+impl<'a, Entity, T, Repr> IntoIterator for &'a IdSlice<Entity, T, Repr> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<Entity, T: PartialEq, Repr> PartialEq for IdSlice<Entity, T, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.raw.eq(&rhs.raw)
+    }
+}
+
+impl<Entity, T: Eq, Repr> Eq for IdSlice<Entity, T, Repr> {}
+
+impl<Entity, T: Hash, Repr> Hash for IdSlice<Entity, T, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+
+impl<Entity, T: fmt::Debug, Repr> fmt::Debug for IdSlice<Entity, T, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+/// `IdVec<Entity, T, Repr>` is a vector of `T` indexed by
+/// `Id<Entity, Repr>` instead of `usize`, which makes it impossible to
+/// look up an element using an index of another table.
+///
+/// ```
+/// use phantom_newtype::{Id, IdVec};
+///
+/// struct User { name: String }
+/// struct Post { author: Id<User, u32> }
+///
+/// let mut users = IdVec::<User, User, u32>::new();
+/// let mut posts = IdVec::<Post, Post, u32>::new();
+///
+/// let alice = users.push(User { name: "alice".to_string() });
+/// let post = posts.push(Post { author: alice });
+///
+/// assert_eq!(users[posts[post].author].name, "alice");
+/// ```
+///
+/// Indexing with ids of other entities doesn't compile:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Id, IdVec};
+///
+/// struct User;
+/// struct Post;
+///
+/// let mut users = IdVec::<User, &str, u32>::new();
+/// users.push("alice");
+///
+/// let _ = users[Id::<Post, u32>::new(0)];
+/// ```
+///
+/// An id vector can't hold more elements than `Repr` can index.
+/// Constructing or extending a longer vector panics,
+/// [`try_from_raw`](#method.try_from_raw) reports it instead.
+///
+/// Id vectors are serialized as sequences of elements.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::IdVec;
+/// use serde_json;
+///
+/// struct User;
+///
+/// let names: IdVec<User, String> = serde_json::from_str(r#"["alice","bob"]"#).unwrap();
+/// assert_eq!(names.len(), 2);
+/// assert_eq!(serde_json::to_string(&names).unwrap(), r#"["alice","bob"]"#);
+/// }
+/// ```
+pub struct IdVec<Entity, T, Repr = usize> {
+    marker: PhantomData<std::sync::Mutex<(Entity, Repr)>>,
+    raw: Vec<T>,
+}
+
+impl<Entity, T, Repr> IdVec<Entity, T, Repr> {
+    /// Constructs an empty vector.
+    pub fn new() -> Self {
+        Self::wrap(Vec::new())
+    }
+
+    /// Constructs an empty vector with the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::wrap(Vec::with_capacity(capacity))
+    }
+
+    fn wrap(raw: Vec<T>) -> Self {
+        Self {
+            marker: PhantomData,
+            raw,
+        }
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_raw(self) -> Vec<T> {
+        self.raw
+    }
+
+    /// Removes the last element and returns it, or `None` if the
+    /// vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw.pop()
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> IdVec<Entity, T, Repr> {
+    /// Wraps a vector of elements. Panics if some elements can't be
+    /// indexed by `Repr`.
+    pub fn from_raw(raw: Vec<T>) -> Self {
+        assert_can_index::<Repr>(raw.len());
+        Self::wrap(raw)
+    }
+
+    /// Wraps a vector of elements, returns the vector back if some
+    /// elements can't be indexed by `Repr`.
+    ///
+    /// ```
+    /// use phantom_newtype::IdVec;
+    ///
+    /// struct Color;
+    ///
+    /// assert!(IdVec::<Color, u32, u8>::try_from_raw(vec![0; 256]).is_ok());
+    /// assert_eq!(IdVec::<Color, u32, u8>::try_from_raw(vec![0; 257]).unwrap_err().len(), 257);
+    /// ```
+    pub fn try_from_raw(raw: Vec<T>) -> Result<Self, Vec<T>> {
+        if can_index::<Repr>(raw.len()) {
+            Ok(Self::wrap(raw))
+        } else {
+            Err(raw)
+        }
+    }
+
+    /// Returns the id the next pushed element will get.
+    pub fn next_id(&self) -> Id<Entity, Repr> {
+        Id::new(Repr::from_usize(self.raw.len()))
+    }
+
+    /// Appends an element and returns its id.
+    pub fn push(&mut self, value: T) -> Id<Entity, Repr> {
+        let id = self.next_id();
+        self.raw.push(value);
+        id
+    }
+}
+
+impl<Entity, T, Repr> Deref for IdVec<Entity, T, Repr> {
+    type Target = IdSlice<Entity, T, Repr>;
+
+    fn deref(&self) -> &Self::Target {
+        IdSlice::from_raw(&self.raw)
+    }
+}
+
+impl<Entity, T, Repr> DerefMut for IdVec<Entity, T, Repr> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        IdSlice::from_raw_mut(&mut self.raw)
+    }
+}
+
+impl<Entity, T, Repr> Default for IdVec<Entity, T, Repr> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> From<Vec<T>> for IdVec<Entity, T, Repr> {
+    fn from(raw: Vec<T>) -> Self {
+        Self::from_raw(raw)
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> FromIterator<T> for IdVec<Entity, T, Repr> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_raw(Vec::from_iter(iter))
+    }
+}
+
+impl<Entity, T, Repr: IndexRepr> Extend<T> for IdVec<Entity, T, Repr> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.raw.extend(iter);
+        assert_can_index::<Repr>(self.raw.len());
+    }
+}
+
+impl<Entity, T, Repr> IntoIterator for IdVec<Entity, T, Repr> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, Entity, T, Repr> IntoIterator for &'a IdVec<Entity, T, Repr> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<Entity, T: Clone, Repr> Clone for IdVec<Entity, T, Repr> {
+    fn clone(&self) -> Self {
+        Self::wrap(self.raw.clone())
+    }
+}
+
+impl<Entity, T: PartialEq, Repr> PartialEq for IdVec<Entity, T, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.raw.eq(&rhs.raw)
+    }
+}
+
+impl<Entity, T: Eq, Repr> Eq for IdVec<Entity, T, Repr> {}
+
+impl<Entity, T: Hash, Repr> Hash for IdVec<Entity, T, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+
+impl<Entity, T: fmt::Debug, Repr> fmt::Debug for IdVec<Entity, T, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Entity, T: Serialize, Repr> Serialize for IdVec<Entity, T, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Entity, T, Repr> Deserialize<'de> for IdVec<Entity, T, Repr>
+where
+    T: Deserialize<'de>,
+    Repr: IndexRepr,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from_raw(Vec::<T>::deserialize(deserializer)?).map_err(|raw| {
+            D::Error::custom(format_args!(
+                "{} elements can't be indexed by the Id representation",
+                raw.len()
+            ))
+        })
+    }
+}
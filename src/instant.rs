@@ -14,6 +14,9 @@
 
 use crate::amount::Amount;
 use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::integer::{IntegerRepr, OffsetRepr};
+use crate::retag::Retag;
+use crate::sealed::Sealed;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::Ordering;
@@ -22,6 +25,76 @@ use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 
+/// Right-hand sides of `Instant<Unit, Repr>` subtraction with explicit
+/// overflow handling: subtracting an instant yields an
+/// [`Amount`](struct.Amount.html), subtracting an amount yields an
+/// instant. This trait is sealed and can't be implemented outside of
+/// this crate.
+pub trait InstantSub<Unit, Repr>: Sealed {
+    /// The result of the subtraction.
+    type Output;
+
+    /// Subtracts `self` from `lhs`, returns `None` on overflow.
+    fn checked_sub_from(self, lhs: Instant<Unit, Repr>) -> Option<Self::Output>;
+
+    /// Subtracts `self` from `lhs` saturating at the bounds of `Repr`.
+    fn saturating_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output;
+
+    /// Subtracts `self` from `lhs` wrapping around at the bounds of
+    /// `Repr`.
+    fn wrapping_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output;
+
+    /// Subtracts `self` from `lhs`, returns the wrapped result and
+    /// whether an overflow occurred.
+    fn overflowing_sub_from(self, lhs: Instant<Unit, Repr>) -> (Self::Output, bool);
+}
+
+impl<Unit, Repr: IntegerRepr> InstantSub<Unit, Repr> for Instant<Unit, Repr> {
+    type Output = Amount<Unit, Repr>;
+
+    fn checked_sub_from(self, lhs: Instant<Unit, Repr>) -> Option<Self::Output> {
+        lhs.0.checked_sub(self.0).map(Amount::new)
+    }
+
+    fn saturating_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output {
+        Amount::new(lhs.0.saturating_sub(self.0))
+    }
+
+    fn wrapping_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output {
+        Amount::new(lhs.0.wrapping_sub(self.0))
+    }
+
+    fn overflowing_sub_from(self, lhs: Instant<Unit, Repr>) -> (Self::Output, bool) {
+        let (repr, overflow) = lhs.0.overflowing_sub(self.0);
+        (Amount::new(repr), overflow)
+    }
+}
+
+impl<Unit, Repr, Repr2> InstantSub<Unit, Repr> for Amount<Unit, Repr2>
+where
+    Repr: OffsetRepr<Repr2>,
+    Repr2: Copy,
+{
+    type Output = Instant<Unit, Repr>;
+
+    fn checked_sub_from(self, lhs: Instant<Unit, Repr>) -> Option<Self::Output> {
+        lhs.0.checked_sub_offset(self.get()).map(Instant::new)
+    }
+
+    fn saturating_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output {
+        Instant::new(lhs.0.saturating_sub_offset(self.get()))
+    }
+
+    fn wrapping_sub_from(self, lhs: Instant<Unit, Repr>) -> Self::Output {
+        Instant::new(lhs.0.wrapping_sub_offset(self.get()))
+    }
+
+    fn overflowing_sub_from(self, lhs: Instant<Unit, Repr>) -> (Self::Output, bool) {
+        let (repr, overflow) = lhs.0.overflowing_sub_offset(self.get());
+        (Instant::new(repr), overflow)
+    }
+}
+
 /// `Instant<Unit>` provides a type-safe way to keep absolute time of
 /// some events, expressed in `Unit`s (CPU ticks, seconds from epoch,
 /// years from birth, etc).
@@ -104,11 +177,11 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 /// enum SecondsFromEpoch {}
 /// type UnixTime = Instant<SecondsFromEpoch, i64>;
 ///
-/// let repr: u64 = 123456;
+/// let repr: i64 = 123456;
 /// let time = UnixTime::from(repr);
 /// assert_eq!(serde_json::to_string(&time).unwrap(), serde_json::to_string(&repr).unwrap());
 ///
-/// let copy: UnitTime = serde_json::from_str(&serde_json::to_string(&time).unwrap()).unwrap();
+/// let copy: UnixTime = serde_json::from_str(&serde_json::to_string(&time).unwrap()).unwrap();
 /// assert_eq!(copy, time);
 /// }
 /// ```
@@ -162,6 +235,43 @@ impl<Unit, Repr> Instant<Unit, Repr> {
     pub const fn new(repr: Repr) -> Instant<Unit, Repr> {
         Instant(repr, PhantomData)
     }
+
+    /// Converts the instant into an instant of another unit. The conversion
+    /// must be allowed by a [`Retag`](trait.Retag.html) implementation.
+    ///
+    /// ```
+    /// use phantom_newtype::{Instant, Retag};
+    ///
+    /// enum ServerMillis {}
+    /// enum SyncedMillis {}
+    /// // The local clock was synchronized with the server.
+    /// impl Retag<SyncedMillis> for ServerMillis {}
+    ///
+    /// let deadline = Instant::<ServerMillis, u64>::from(1_600_000);
+    /// let local: Instant<SyncedMillis, u64> = deadline.retag();
+    /// assert_eq!(local, Instant::from(1_600_000));
+    /// ```
+    pub fn retag<To>(self) -> Instant<To, Repr>
+    where
+        Unit: Retag<To>,
+    {
+        Instant::new(self.0)
+    }
+
+    /// Drops the unit and returns the underlying representation.
+    ///
+    /// ```
+    /// use phantom_newtype::Instant;
+    ///
+    /// enum UnixSeconds {}
+    ///
+    /// let when = Instant::<UnixSeconds, u64>::from(86_400);
+    /// let system = std::time::UNIX_EPOCH + std::time::Duration::from_secs(when.forget_tag());
+    /// assert_eq!(system.duration_since(std::time::UNIX_EPOCH).unwrap().as_secs(), 86_400);
+    /// ```
+    pub fn forget_tag(self) -> Repr {
+        self.0
+    }
 }
 
 impl<Unit: Default, Repr: Copy> Instant<Unit, Repr> {
@@ -209,6 +319,240 @@ where
     }
 }
 
+impl<Unit, Repr> Instant<Unit, Repr>
+where
+    Repr: IntegerRepr + OffsetRepr<Repr::Signed>,
+{
+    /// Returns the signed amount of units elapsed since `earlier`. The
+    /// amount is negative if `earlier` is actually later, e.g. due to a
+    /// clock skew.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    /// type Delta = Amount<Millis, i64>;
+    ///
+    /// let sent = Timestamp::from(1000);
+    /// let received = Timestamp::from(990);
+    ///
+    /// assert_eq!(received.signed_since(sent), Delta::from(-10));
+    /// assert_eq!(sent.signed_since(received), Delta::from(10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the difference doesn't fit into the signed counterpart
+    /// of `Repr`:
+    ///
+    /// ```should_panic
+    /// use phantom_newtype::Instant;
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    ///
+    /// let _ = Timestamp::from(u64::MAX).signed_since(Timestamp::from(0));
+    /// ```
+    pub fn signed_since(self, earlier: Self) -> Amount<Unit, Repr::Signed> {
+        Amount::new(
+            self.0
+                .checked_offset_from(earlier.0)
+                .expect("attempt to subtract with overflow"),
+        )
+    }
+
+    /// Shifts the instant by a signed amount. Panics if the result
+    /// doesn't fit into `Repr`. The `checked_add` family accepts signed
+    /// amounts as well.
+    ///
+    /// `Instant + Amount<Unit, Signed>` can't be expressed as an
+    /// operator because it would overlap with `Instant<Unit, Repr> +
+    /// Amount<Unit, Repr2>` that allows instants like `SystemTime` to
+    /// be shifted by `Duration`s.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    /// type Delta = Amount<Millis, i64>;
+    ///
+    /// let t = Timestamp::from(1000);
+    ///
+    /// assert_eq!(t.add_signed(Delta::from(-10)), Timestamp::from(990));
+    /// assert_eq!(t.add_signed(Delta::from(10)), Timestamp::from(1010));
+    /// assert_eq!(t.checked_add(Delta::from(-1001)), None);
+    /// assert_eq!(t.saturating_add(Delta::from(-1001)), Timestamp::from(0));
+    /// ```
+    pub fn add_signed(&self, amount: Amount<Unit, Repr::Signed>) -> Self {
+        self.checked_add(amount)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl<Unit, Repr: Copy> Instant<Unit, Repr> {
+    /// Shifts the instant forward, returning `None` on overflow. The
+    /// amount can have the same representation as the instant or,
+    /// for unsigned instants, the signed counterpart of it.
+    ///
+    /// The `checked_sub` family accepts either an amount or another
+    /// instant, like the `-` operator does.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    /// type Elapsed = Amount<Millis, u64>;
+    /// type Delta = Amount<Millis, i64>;
+    ///
+    /// let t = Timestamp::from(100);
+    ///
+    /// assert_eq!(t.checked_add(Elapsed::from(5)), Some(Timestamp::from(105)));
+    /// assert_eq!(t.checked_add(Delta::from(-101)), None);
+    /// assert_eq!(t.saturating_add(Delta::from(-101)), Timestamp::from(0));
+    /// assert_eq!(t.checked_sub(Elapsed::from(101)), None);
+    /// assert_eq!(t.checked_sub(Timestamp::from(40)), Some(Elapsed::from(60)));
+    /// assert_eq!(t.checked_sub(Timestamp::from(140)), None);
+    /// assert_eq!(t.saturating_sub(Timestamp::from(140)), Elapsed::from(0));
+    /// assert_eq!(t.overflowing_sub(Timestamp::from(101)), (Elapsed::from(u64::MAX), true));
+    /// assert_eq!(t.wrapping_mul(u64::MAX), Timestamp::from(100u64.wrapping_mul(u64::MAX)));
+    /// ```
+    pub fn checked_add<Repr2>(self, rhs: Amount<Unit, Repr2>) -> Option<Self>
+    where
+        Repr: OffsetRepr<Repr2>,
+        Repr2: Copy,
+    {
+        self.0.checked_add_offset(rhs.get()).map(Self::new)
+    }
+
+    /// Subtracts an amount or an instant, returning `None` on
+    /// overflow.
+    pub fn checked_sub<Rhs>(self, rhs: Rhs) -> Option<Rhs::Output>
+    where
+        Rhs: InstantSub<Unit, Repr>,
+    {
+        rhs.checked_sub_from(self)
+    }
+
+    /// Shifts the instant forward, saturating at the bounds of `Repr`.
+    pub fn saturating_add<Repr2>(self, rhs: Amount<Unit, Repr2>) -> Self
+    where
+        Repr: OffsetRepr<Repr2>,
+        Repr2: Copy,
+    {
+        Self::new(self.0.saturating_add_offset(rhs.get()))
+    }
+
+    /// Subtracts an amount or an instant, saturating at the bounds of
+    /// `Repr`.
+    pub fn saturating_sub<Rhs>(self, rhs: Rhs) -> Rhs::Output
+    where
+        Rhs: InstantSub<Unit, Repr>,
+    {
+        rhs.saturating_sub_from(self)
+    }
+
+    /// Shifts the instant forward, wrapping around at the bounds of
+    /// `Repr`.
+    pub fn wrapping_add<Repr2>(self, rhs: Amount<Unit, Repr2>) -> Self
+    where
+        Repr: OffsetRepr<Repr2>,
+        Repr2: Copy,
+    {
+        Self::new(self.0.wrapping_add_offset(rhs.get()))
+    }
+
+    /// Subtracts an amount or an instant, wrapping around at the bounds
+    /// of `Repr`.
+    pub fn wrapping_sub<Rhs>(self, rhs: Rhs) -> Rhs::Output
+    where
+        Rhs: InstantSub<Unit, Repr>,
+    {
+        rhs.wrapping_sub_from(self)
+    }
+
+    /// Shifts the instant forward, returning the wrapped result and
+    /// whether an overflow occurred.
+    pub fn overflowing_add<Repr2>(self, rhs: Amount<Unit, Repr2>) -> (Self, bool)
+    where
+        Repr: OffsetRepr<Repr2>,
+        Repr2: Copy,
+    {
+        let (repr, overflow) = self.0.overflowing_add_offset(rhs.get());
+        (Self::new(repr), overflow)
+    }
+
+    /// Subtracts an amount or an instant, returning the wrapped result
+    /// and whether an overflow occurred.
+    pub fn overflowing_sub<Rhs>(self, rhs: Rhs) -> (Rhs::Output, bool)
+    where
+        Rhs: InstantSub<Unit, Repr>,
+    {
+        rhs.overflowing_sub_from(self)
+    }
+}
+
+impl<Unit, Repr: IntegerRepr> Instant<Unit, Repr> {
+    /// Returns the amount of units elapsed since `earlier`, `None` if
+    /// `earlier` is later than `self`.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    ///
+    /// assert_eq!(Timestamp::from(5).checked_since(Timestamp::from(3)), Some(Amount::from(2)));
+    /// assert_eq!(Timestamp::from(3).checked_since(Timestamp::from(5)), None);
+    /// ```
+    pub fn checked_since(self, earlier: Self) -> Option<Amount<Unit, Repr>> {
+        self.checked_sub(earlier)
+    }
+
+    /// Returns the amount of units elapsed since `earlier`, zero if
+    /// `earlier` is later than `self`.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    ///
+    /// assert_eq!(Timestamp::from(5).saturating_since(Timestamp::from(3)), Amount::from(2));
+    /// assert_eq!(Timestamp::from(3).saturating_since(Timestamp::from(5)), Amount::from(0));
+    /// ```
+    pub fn saturating_since(self, earlier: Self) -> Amount<Unit, Repr> {
+        self.saturating_sub(earlier)
+    }
+
+    /// Multiplies the instant by a scalar, returning `None` on
+    /// overflow.
+    pub fn checked_mul(self, rhs: Repr) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self::new)
+    }
+
+    /// Multiplies the instant by a scalar, saturating at the bounds of
+    /// `Repr`.
+    pub fn saturating_mul(self, rhs: Repr) -> Self {
+        Self::new(self.0.saturating_mul(rhs))
+    }
+
+    /// Multiplies the instant by a scalar, wrapping around at the
+    /// bounds of `Repr`.
+    pub fn wrapping_mul(self, rhs: Repr) -> Self {
+        Self::new(self.0.wrapping_mul(rhs))
+    }
+
+    /// Multiplies the instant by a scalar, returning the wrapped result
+    /// and whether an overflow occurred.
+    pub fn overflowing_mul(self, rhs: Repr) -> (Self, bool) {
+        let (repr, overflow) = self.0.overflowing_mul(rhs);
+        (Self::new(repr), overflow)
+    }
+}
+
 impl<Unit, Repr: Copy> From<Repr> for Instant<Unit, Repr> {
     fn from(repr: Repr) -> Self {
         Self::new(repr)
@@ -217,7 +561,7 @@ impl<Unit, Repr: Copy> From<Repr> for Instant<Unit, Repr> {
 
 impl<Unit, Repr: Copy> Clone for Instant<Unit, Repr> {
     fn clone(&self) -> Self {
-        Instant(self.0, PhantomData)
+        *self
     }
 }
 
@@ -379,15 +723,15 @@ mod tests {
     #[test]
     fn test_complex_instant_arithmetics() {
         enum Seconds {}
-        enum UTC {}
+        enum Utc {}
 
         type Timestamp = Instant<Seconds, i64>;
         type TsDiff = Amount<Seconds, i64>;
-        type Date = Instant<UTC, Timestamp>;
+        type Date = Instant<Utc, Timestamp>;
 
         let epoch = Date::new(Timestamp::new(0));
         let date = Date::new(Timestamp::new(123456789));
-        let span = Amount::<UTC, TsDiff>::new(TsDiff::from(123456789));
+        let span = Amount::<Utc, TsDiff>::new(TsDiff::from(123456789));
 
         assert_eq!(date - epoch, span);
         assert_eq!(date - span, epoch);
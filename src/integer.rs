@@ -0,0 +1,266 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::sealed::Sealed;
+use std::convert::TryFrom;
+
+/// Integer representations supporting explicit overflow handling,
+/// implemented by all primitive integer types. The methods forward to
+/// the inherent methods of the primitive types with the same names.
+/// This trait is sealed and can't be implemented outside of this
+/// crate.
+pub trait IntegerRepr: Copy + Sealed {
+    /// The signed integer type of the same size, `Self` for signed
+    /// types.
+    type Signed: Copy;
+
+    /// Adds two values, returns `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Subtracts two values, returns `None` on overflow.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Multiplies two values, returns `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Adds two values saturating at the bounds of the type.
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Subtracts two values saturating at the bounds of the type.
+    fn saturating_sub(self, rhs: Self) -> Self;
+
+    /// Multiplies two values saturating at the bounds of the type.
+    fn saturating_mul(self, rhs: Self) -> Self;
+
+    /// Adds two values wrapping around at the bounds of the type.
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /// Subtracts two values wrapping around at the bounds of the type.
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /// Multiplies two values wrapping around at the bounds of the type.
+    fn wrapping_mul(self, rhs: Self) -> Self;
+
+    /// Adds two values, returns the wrapped result and whether an
+    /// overflow occurred.
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+
+    /// Subtracts two values, returns the wrapped result and whether an
+    /// overflow occurred.
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool);
+
+    /// Multiplies two values, returns the wrapped result and whether an
+    /// overflow occurred.
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+}
+
+macro_rules! forward_integer_ops {
+    ($repr:ident, $($op:ident -> $ret:ty),*) => {
+        $(
+            fn $op(self, rhs: Self) -> $ret {
+                $repr::$op(self, rhs)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_integer_repr {
+    ($($repr:ident($signed:ident)),*) => {
+        $(
+            impl IntegerRepr for $repr {
+                type Signed = $signed;
+
+                forward_integer_ops!(
+                    $repr,
+                    checked_add -> Option<Self>,
+                    checked_sub -> Option<Self>,
+                    checked_mul -> Option<Self>,
+                    saturating_add -> Self,
+                    saturating_sub -> Self,
+                    saturating_mul -> Self,
+                    wrapping_add -> Self,
+                    wrapping_sub -> Self,
+                    wrapping_mul -> Self,
+                    overflowing_add -> (Self, bool),
+                    overflowing_sub -> (Self, bool),
+                    overflowing_mul -> (Self, bool)
+                );
+            }
+        )*
+    };
+}
+
+impl_integer_repr!(
+    u8(i8),
+    u16(i16),
+    u32(i32),
+    u64(i64),
+    u128(i128),
+    usize(isize),
+    i8(i8),
+    i16(i16),
+    i32(i32),
+    i64(i64),
+    i128(i128),
+    isize(isize)
+);
+
+/// Integer representations that can be shifted by an `Offset` with
+/// explicit overflow handling. Every integer type can be shifted by
+/// itself, unsigned types can also be shifted by their signed
+/// counterparts. This trait is sealed and can't be implemented outside
+/// of this crate.
+pub trait OffsetRepr<Offset>: Copy + Sealed {
+    /// Returns the offset that shifts `origin` to `self`, `None` if it
+    /// doesn't fit into `Offset`.
+    fn checked_offset_from(self, origin: Self) -> Option<Offset>;
+
+    /// Shifts the value forward, returns `None` on overflow.
+    fn checked_add_offset(self, offset: Offset) -> Option<Self>;
+
+    /// Shifts the value backward, returns `None` on overflow.
+    fn checked_sub_offset(self, offset: Offset) -> Option<Self>;
+
+    /// Shifts the value forward saturating at the bounds of the type.
+    fn saturating_add_offset(self, offset: Offset) -> Self;
+
+    /// Shifts the value backward saturating at the bounds of the type.
+    fn saturating_sub_offset(self, offset: Offset) -> Self;
+
+    /// Shifts the value forward wrapping around at the bounds of the
+    /// type.
+    fn wrapping_add_offset(self, offset: Offset) -> Self;
+
+    /// Shifts the value backward wrapping around at the bounds of the
+    /// type.
+    fn wrapping_sub_offset(self, offset: Offset) -> Self;
+
+    /// Shifts the value forward, returns the wrapped result and
+    /// whether an overflow occurred.
+    fn overflowing_add_offset(self, offset: Offset) -> (Self, bool);
+
+    /// Shifts the value backward, returns the wrapped result and
+    /// whether an overflow occurred.
+    fn overflowing_sub_offset(self, offset: Offset) -> (Self, bool);
+}
+
+impl<Repr: IntegerRepr> OffsetRepr<Repr> for Repr {
+    fn checked_offset_from(self, origin: Repr) -> Option<Repr> {
+        self.checked_sub(origin)
+    }
+
+    fn checked_add_offset(self, offset: Repr) -> Option<Self> {
+        self.checked_add(offset)
+    }
+
+    fn checked_sub_offset(self, offset: Repr) -> Option<Self> {
+        self.checked_sub(offset)
+    }
+
+    fn saturating_add_offset(self, offset: Repr) -> Self {
+        self.saturating_add(offset)
+    }
+
+    fn saturating_sub_offset(self, offset: Repr) -> Self {
+        self.saturating_sub(offset)
+    }
+
+    fn wrapping_add_offset(self, offset: Repr) -> Self {
+        self.wrapping_add(offset)
+    }
+
+    fn wrapping_sub_offset(self, offset: Repr) -> Self {
+        self.wrapping_sub(offset)
+    }
+
+    fn overflowing_add_offset(self, offset: Repr) -> (Self, bool) {
+        self.overflowing_add(offset)
+    }
+
+    fn overflowing_sub_offset(self, offset: Repr) -> (Self, bool) {
+        self.overflowing_sub(offset)
+    }
+}
+
+// The exact results of shifting unsigned integers up to 64 bits by
+// signed ones always fit into `i128`.
+macro_rules! impl_signed_offset_repr {
+    ($($repr:ident($signed:ident)),*) => {
+        $(
+            impl OffsetRepr<$signed> for $repr {
+                fn checked_offset_from(self, origin: Self) -> Option<$signed> {
+                    $signed::try_from(self as i128 - origin as i128).ok()
+                }
+
+                fn checked_add_offset(self, offset: $signed) -> Option<Self> {
+                    $repr::try_from(self as i128 + offset as i128).ok()
+                }
+
+                fn checked_sub_offset(self, offset: $signed) -> Option<Self> {
+                    $repr::try_from(self as i128 - offset as i128).ok()
+                }
+
+                fn saturating_add_offset(self, offset: $signed) -> Self {
+                    (self as i128 + offset as i128).clamp(0, $repr::MAX as i128) as $repr
+                }
+
+                fn saturating_sub_offset(self, offset: $signed) -> Self {
+                    (self as i128 - offset as i128).clamp(0, $repr::MAX as i128) as $repr
+                }
+
+                fn wrapping_add_offset(self, offset: $signed) -> Self {
+                    self.wrapping_add(offset as $repr)
+                }
+
+                fn wrapping_sub_offset(self, offset: $signed) -> Self {
+                    self.wrapping_sub(offset as $repr)
+                }
+
+                fn overflowing_add_offset(self, offset: $signed) -> (Self, bool) {
+                    let overflow = self.checked_add_offset(offset).is_none();
+                    (self.wrapping_add_offset(offset), overflow)
+                }
+
+                fn overflowing_sub_offset(self, offset: $signed) -> (Self, bool) {
+                    let overflow = self.checked_sub_offset(offset).is_none();
+                    (self.wrapping_sub_offset(offset), overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_offset_repr!(u8(i8), u16(i16), u32(i32), u64(i64), usize(isize));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_offsets() {
+        assert_eq!(10u8.checked_add_offset(-3i8), Some(7));
+        assert_eq!(10u8.checked_add_offset(-11i8), None);
+        assert_eq!(250u8.checked_add_offset(10i8), None);
+        assert_eq!(10u8.checked_sub_offset(-128i8), Some(138));
+        assert_eq!(10u8.saturating_add_offset(-11i8), 0);
+        assert_eq!(250u8.saturating_sub_offset(-10i8), 255);
+        assert_eq!(10u8.wrapping_add_offset(-11i8), 255);
+        assert_eq!(10u8.wrapping_sub_offset(-128i8), 138);
+        assert_eq!(0u8.overflowing_sub_offset(1i8), (255, true));
+        assert_eq!(0u8.overflowing_add_offset(1i8), (1, false));
+        assert_eq!(10u8.checked_offset_from(138u8), Some(-128i8));
+        assert_eq!(OffsetRepr::<i8>::checked_offset_from(10u8, 139), None);
+        assert_eq!(OffsetRepr::<i8>::checked_offset_from(255u8, 0), None);
+    }
+}
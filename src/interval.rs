@@ -0,0 +1,385 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::{self, Ordering};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{AddAssign, Sub};
+
+/// Describes which ends of an [`Interval`](struct.Interval.html) are
+/// included in it. Implemented by [`HalfOpen`](enum.HalfOpen.html) and
+/// [`Closed`](enum.Closed.html).
+pub trait IntervalBounds {
+    /// Whether the end of the interval belongs to the interval.
+    const INCLUDES_END: bool;
+}
+
+/// Bounds of intervals that include the start but not the end,
+/// `[start, end)`.
+pub enum HalfOpen {}
+
+/// Bounds of intervals that include both the start and the end,
+/// `[start, end]`.
+pub enum Closed {}
+
+impl IntervalBounds for HalfOpen {
+    const INCLUDES_END: bool = false;
+}
+
+impl IntervalBounds for Closed {
+    const INCLUDES_END: bool = true;
+}
+
+/// `Interval<Unit, Repr, Bounds>` is a range of
+/// [`Instant`](struct.Instant.html)s measured in `Unit`s, e.g. a
+/// validity window or a billing period. By default intervals are
+/// half-open: the start belongs to the interval, the end doesn't.
+///
+/// Intervals can only be constructed if `start <= end`:
+///
+/// ```
+/// use phantom_newtype::{Instant, Interval};
+///
+/// enum SecondsFromEpoch {}
+/// type UnixTime = Instant<SecondsFromEpoch, u64>;
+/// type Period = Interval<SecondsFromEpoch, u64>;
+///
+/// assert!(Period::new(UnixTime::from(10), UnixTime::from(20)).is_some());
+/// assert!(Period::new(UnixTime::from(20), UnixTime::from(10)).is_none());
+/// ```
+///
+/// The bounds determine which points belong to the interval:
+///
+/// ```
+/// use phantom_newtype::{Closed, Instant, Interval};
+///
+/// enum Days {}
+/// type Day = Instant<Days, u32>;
+///
+/// let half_open = Interval::<Days, u32>::new(Day::from(1), Day::from(7)).unwrap();
+/// let closed = Interval::<Days, u32, Closed>::new(Day::from(1), Day::from(7)).unwrap();
+///
+/// assert!(half_open.contains(Day::from(1)));
+/// assert!(!half_open.contains(Day::from(7)));
+/// assert!(closed.contains(Day::from(7)));
+/// assert_eq!(half_open.length(), closed.length());
+/// ```
+///
+/// Intervals of different units can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Instant, Interval};
+///
+/// enum Seconds {}
+/// enum Days {}
+///
+/// let days = Interval::<Days, u32>::new(Instant::from(1), Instant::from(7)).unwrap();
+/// days.contains(Instant::<Seconds, u32>::from(5));
+/// ```
+///
+/// Intervals are serialized as a pair `[start, end]`, deserialization
+/// fails if `start > end`.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{Instant, Interval};
+/// use serde_json;
+///
+/// enum Days {}
+/// type Week = Interval<Days, u32>;
+///
+/// let week = Week::new(Instant::from(1), Instant::from(8)).unwrap();
+/// assert_eq!(serde_json::to_string(&week).unwrap(), "[1,8]");
+/// assert_eq!(serde_json::from_str::<Week>("[1,8]").unwrap(), week);
+/// assert!(serde_json::from_str::<Week>("[8,1]").is_err());
+/// }
+/// ```
+pub struct Interval<Unit, Repr, Bounds = HalfOpen> {
+    start: Instant<Unit, Repr>,
+    end: Instant<Unit, Repr>,
+    bounds: PhantomData<Bounds>,
+}
+
+impl<Unit, Repr, Bounds> Interval<Unit, Repr, Bounds>
+where
+    Repr: Ord + Copy,
+    Bounds: IntervalBounds,
+{
+    /// Constructs a new interval, returns `None` if `start > end`.
+    pub fn new(start: Instant<Unit, Repr>, end: Instant<Unit, Repr>) -> Option<Self> {
+        if start <= end {
+            Some(Self::new_unchecked(start, end))
+        } else {
+            None
+        }
+    }
+
+    fn new_unchecked(start: Instant<Unit, Repr>, end: Instant<Unit, Repr>) -> Self {
+        Self {
+            start,
+            end,
+            bounds: PhantomData,
+        }
+    }
+
+    /// Returns the start of the interval.
+    pub fn start(&self) -> Instant<Unit, Repr> {
+        self.start
+    }
+
+    /// Returns the end of the interval.
+    pub fn end(&self) -> Instant<Unit, Repr> {
+        self.end
+    }
+
+    /// Returns `true` if the interval contains no points. Closed
+    /// intervals are never empty.
+    pub fn is_empty(&self) -> bool {
+        !Bounds::INCLUDES_END && self.start == self.end
+    }
+
+    /// Checks whether the instant belongs to the interval.
+    pub fn contains(&self, instant: Instant<Unit, Repr>) -> bool {
+        self.start <= instant
+            && (instant < self.end || (Bounds::INCLUDES_END && instant == self.end))
+    }
+
+    /// Checks whether the intervals have at least one common point.
+    ///
+    /// ```
+    /// use phantom_newtype::{Closed, Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let i = |s, e| Interval::<Days, u32>::new(Instant::from(s), Instant::from(e)).unwrap();
+    /// let c = |s, e| Interval::<Days, u32, Closed>::new(Instant::from(s), Instant::from(e)).unwrap();
+    ///
+    /// assert!(i(1, 5).overlaps(&i(4, 8)));
+    /// assert!(!i(1, 5).overlaps(&i(5, 8)));
+    /// assert!(c(1, 5).overlaps(&c(5, 8)));
+    /// ```
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the common part of the intervals, or `None` if the
+    /// intervals don't overlap.
+    ///
+    /// ```
+    /// use phantom_newtype::{Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let i = |s, e| Interval::<Days, u32>::new(Instant::from(s), Instant::from(e)).unwrap();
+    ///
+    /// assert_eq!(i(1, 5).intersection(&i(3, 8)), Some(i(3, 5)));
+    /// assert_eq!(i(1, 5).intersection(&i(5, 8)), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = cmp::max(self.start, other.start);
+        let end = cmp::min(self.end, other.end);
+        if start < end || (Bounds::INCLUDES_END && start == end) {
+            Some(Self::new_unchecked(start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the interval covering both intervals if they overlap
+    /// or are adjacent, `None` otherwise.
+    ///
+    /// ```
+    /// use phantom_newtype::{Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let i = |s, e| Interval::<Days, u32>::new(Instant::from(s), Instant::from(e)).unwrap();
+    ///
+    /// assert_eq!(i(1, 5).union(&i(5, 8)), Some(i(1, 8)));
+    /// assert_eq!(i(1, 5).union(&i(6, 8)), None);
+    /// ```
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.start <= other.end && other.start <= self.end {
+            Some(Self::new_unchecked(
+                cmp::min(self.start, other.start),
+                cmp::max(self.end, other.end),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the interval moved by the specified amount.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let i = |s, e| Interval::<Days, i32>::new(Instant::from(s), Instant::from(e)).unwrap();
+    ///
+    /// assert_eq!(i(1, 8).shift(Amount::from(7)), i(8, 15));
+    /// assert_eq!(i(1, 8).shift(Amount::from(-1)), i(0, 7));
+    /// ```
+    pub fn shift<Repr2>(&self, by: Amount<Unit, Repr2>) -> Self
+    where
+        Repr: AddAssign<Repr2>,
+        Repr2: Copy,
+    {
+        Self::new_unchecked(self.start + by, self.end + by)
+    }
+}
+
+impl<Unit, Repr> Interval<Unit, Repr, HalfOpen>
+where
+    Repr: Ord + Copy,
+{
+    /// Splits the interval into two adjacent intervals at the
+    /// specified instant. Returns `None` if the instant is outside of
+    /// `[start, end]`.
+    ///
+    /// ```
+    /// use phantom_newtype::{Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let i = |s, e| Interval::<Days, u32>::new(Instant::from(s), Instant::from(e)).unwrap();
+    ///
+    /// assert_eq!(i(1, 8).split_at(Instant::from(5)), Some((i(1, 5), i(5, 8))));
+    /// assert_eq!(i(1, 8).split_at(Instant::from(9)), None);
+    /// ```
+    ///
+    /// Closed intervals can't be split, both halves would contain the
+    /// split instant:
+    ///
+    /// ```compile_fail
+    /// use phantom_newtype::{Closed, Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let days = Interval::<Days, u32, Closed>::new(Instant::from(1), Instant::from(8)).unwrap();
+    ///
+    /// let _ = days.split_at(Instant::from(5));
+    /// ```
+    pub fn split_at(&self, instant: Instant<Unit, Repr>) -> Option<(Self, Self)> {
+        if self.start <= instant && instant <= self.end {
+            Some((
+                Self::new_unchecked(self.start, instant),
+                Self::new_unchecked(instant, self.end),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<Unit, Repr, Bounds> Interval<Unit, Repr, Bounds>
+where
+    Repr: Sub + Copy,
+{
+    /// Returns the amount of units between the start and the end of
+    /// the interval.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Instant, Interval};
+    ///
+    /// enum Days {}
+    /// let week = Interval::<Days, u32>::new(Instant::from(1), Instant::from(8)).unwrap();
+    ///
+    /// assert_eq!(week.length(), Amount::from(7));
+    /// ```
+    pub fn length(&self) -> Amount<Unit, <Repr as Sub>::Output> {
+        self.end - self.start
+    }
+}
+
+impl<Unit, Repr: Copy, Bounds> Clone for Interval<Unit, Repr, Bounds> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Unit, Repr: Copy, Bounds> Copy for Interval<Unit, Repr, Bounds> {}
+
+impl<Unit, Repr: PartialEq, Bounds> PartialEq for Interval<Unit, Repr, Bounds> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.start.eq(&rhs.start) && self.end.eq(&rhs.end)
+    }
+}
+
+impl<Unit, Repr: Eq, Bounds> Eq for Interval<Unit, Repr, Bounds> {}
+
+impl<Unit, Repr: PartialOrd, Bounds> PartialOrd for Interval<Unit, Repr, Bounds> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        match self.start.partial_cmp(&rhs.start) {
+            Some(Ordering::Equal) => self.end.partial_cmp(&rhs.end),
+            ord => ord,
+        }
+    }
+}
+
+impl<Unit, Repr: Ord, Bounds> Ord for Interval<Unit, Repr, Bounds> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.start
+            .cmp(&rhs.start)
+            .then_with(|| self.end.cmp(&rhs.end))
+    }
+}
+
+impl<Unit, Repr: Hash, Bounds> Hash for Interval<Unit, Repr, Bounds> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.end.hash(state);
+    }
+}
+
+impl<Unit, Repr, Bounds> fmt::Debug for Interval<Unit, Repr, Bounds>
+where
+    Repr: fmt::Debug,
+    Bounds: IntervalBounds,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let close = if Bounds::INCLUDES_END { ']' } else { ')' };
+        write!(f, "[{:?}, {:?}{}", self.start, self.end, close)
+    }
+}
+
+impl<Unit, Repr, Bounds> fmt::Display for Interval<Unit, Repr, Bounds>
+where
+    Repr: fmt::Display,
+    Bounds: IntervalBounds,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let close = if Bounds::INCLUDES_END { ']' } else { ')' };
+        write!(f, "[{}, {}{}", self.start, self.end, close)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Unit, Repr: Serialize, Bounds> Serialize for Interval<Unit, Repr, Bounds> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.start, &self.end).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Unit, Repr, Bounds> Deserialize<'de> for Interval<Unit, Repr, Bounds>
+where
+    Repr: Deserialize<'de> + Ord + Copy,
+    Bounds: IntervalBounds,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (start, end) = <(Instant<Unit, Repr>, Instant<Unit, Repr>)>::deserialize(deserializer)?;
+        Self::new(start, end).ok_or_else(|| D::Error::custom("interval start is after its end"))
+    }
+}
@@ -0,0 +1,365 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use crate::interval::Interval;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{AddAssign, Sub};
+
+/// `IntervalSet<Unit, Repr>` is a set of
+/// [`Instant`](struct.Instant.html)s represented as a sorted list of
+/// disjoint half-open [`Interval`](struct.Interval.html)s.
+///
+/// The set is always kept normalized: overlapping and adjacent
+/// intervals are merged, empty intervals are dropped.
+///
+/// ```
+/// use phantom_newtype::{Instant, Interval, IntervalSet};
+///
+/// enum Minutes {}
+/// type Period = Interval<Minutes, u32>;
+/// let i = |s, e| Period::new(Instant::from(s), Instant::from(e)).unwrap();
+///
+/// let mut downtime = IntervalSet::new();
+/// downtime.insert(i(10, 20));
+/// downtime.insert(i(30, 40));
+/// downtime.insert(i(15, 30));
+/// downtime.insert(i(50, 50));
+///
+/// assert_eq!(downtime.iter().collect::<Vec<_>>(), vec![&i(10, 40)]);
+///
+/// downtime.remove(i(20, 25));
+/// assert_eq!(downtime.iter().collect::<Vec<_>>(), vec![&i(10, 20), &i(25, 40)]);
+/// assert!(downtime.contains(Instant::from(10)));
+/// assert!(!downtime.contains(Instant::from(20)));
+/// ```
+///
+/// Sets support union, intersection and difference, and can report
+/// uncovered gaps and the total covered length:
+///
+/// ```
+/// use phantom_newtype::{Amount, Instant, Interval, IntervalSet};
+///
+/// enum Minutes {}
+/// type Period = Interval<Minutes, u32>;
+/// let i = |s, e| Period::new(Instant::from(s), Instant::from(e)).unwrap();
+///
+/// let alice: IntervalSet<_, _> = vec![i(9, 12), i(13, 17)].into_iter().collect();
+/// let bob: IntervalSet<_, _> = vec![i(10, 14)].into_iter().collect();
+///
+/// assert_eq!(alice.intersection(&bob), vec![i(10, 12), i(13, 14)].into_iter().collect());
+/// assert_eq!(alice.union(&bob), vec![i(9, 17)].into_iter().collect());
+/// assert_eq!(alice.difference(&bob), vec![i(9, 10), i(14, 17)].into_iter().collect());
+///
+/// assert_eq!(alice.gaps(i(8, 18)), vec![i(8, 9), i(12, 13), i(17, 18)].into_iter().collect());
+/// assert_eq!(alice.total_length(), Amount::from(7));
+/// ```
+///
+/// Interval sets are serialized as a sequence of intervals. The
+/// deserialized set is normalized.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{Instant, Interval, IntervalSet};
+/// use serde_json;
+///
+/// enum Minutes {}
+/// type Schedule = IntervalSet<Minutes, u32>;
+///
+/// let schedule: Schedule = serde_json::from_str("[[5,7],[1,3],[2,4]]").unwrap();
+/// assert_eq!(serde_json::to_string(&schedule).unwrap(), "[[1,4],[5,7]]");
+/// }
+/// ```
+pub struct IntervalSet<Unit, Repr> {
+    intervals: Vec<Interval<Unit, Repr>>,
+}
+
+impl<Unit, Repr> IntervalSet<Unit, Repr> {
+    /// Constructs an empty set.
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Returns the number of disjoint intervals in the set.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns `true` if the set contains no instants.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Iterates over the disjoint intervals of the set in ascending
+    /// order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<Unit, Repr>> {
+        self.intervals.iter()
+    }
+}
+
+impl<Unit, Repr> IntervalSet<Unit, Repr>
+where
+    Repr: Ord + Copy,
+{
+    /// Adds all the instants of the interval to the set.
+    pub fn insert(&mut self, interval: Interval<Unit, Repr>) {
+        if interval.is_empty() {
+            return;
+        }
+        let (start, end) = (interval.start(), interval.end());
+        let lo = self.intervals.partition_point(|x| x.end() < start);
+        let hi = self.intervals.partition_point(|x| x.start() <= end);
+        let merged = if lo < hi {
+            Interval::new(
+                cmp::min(start, self.intervals[lo].start()),
+                cmp::max(end, self.intervals[hi - 1].end()),
+            )
+            .expect("merged interval is well-formed")
+        } else {
+            interval
+        };
+        self.intervals.splice(lo..hi, Some(merged));
+    }
+
+    /// Removes all the instants of the interval from the set.
+    pub fn remove(&mut self, interval: Interval<Unit, Repr>) {
+        if interval.is_empty() {
+            return;
+        }
+        let (start, end) = (interval.start(), interval.end());
+        let lo = self.intervals.partition_point(|x| x.end() <= start);
+        let hi = self.intervals.partition_point(|x| x.start() < end);
+        if lo >= hi {
+            return;
+        }
+        let left = Interval::new(self.intervals[lo].start(), start).filter(|x| !x.is_empty());
+        let right = Interval::new(end, self.intervals[hi - 1].end()).filter(|x| !x.is_empty());
+        self.intervals.splice(lo..hi, left.into_iter().chain(right));
+    }
+
+    /// Checks whether the instant belongs to the set.
+    pub fn contains(&self, instant: Instant<Unit, Repr>) -> bool {
+        let idx = self.intervals.partition_point(|x| x.end() <= instant);
+        matches!(self.intervals.get(idx), Some(x) if x.contains(instant))
+    }
+
+    /// Returns the set of instants belonging to either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in other.iter() {
+            result.insert(*interval);
+        }
+        result
+    }
+
+    /// Returns the set of instants belonging to both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(common) = a.intersection(&b) {
+                result.intervals.push(common);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        result
+    }
+
+    /// Returns the set of instants belonging to this set but not to
+    /// the other one.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in other.iter() {
+            result.remove(*interval);
+        }
+        result
+    }
+
+    /// Returns the set of instants within the bound that don't belong
+    /// to this set.
+    pub fn gaps(&self, within: Interval<Unit, Repr>) -> Self {
+        let mut result = Self::new();
+        result.insert(within);
+        result.difference(self)
+    }
+}
+
+impl<Unit, Repr> IntervalSet<Unit, Repr>
+where
+    Repr: Sub + Copy,
+    <Repr as Sub>::Output: AddAssign + Default + Copy,
+{
+    /// Returns the total amount of units covered by the set.
+    pub fn total_length(&self) -> Amount<Unit, <Repr as Sub>::Output> {
+        let mut total = Amount::new(Default::default());
+        for interval in self.intervals.iter() {
+            total += interval.length();
+        }
+        total
+    }
+}
+
+impl<Unit, Repr> Default for IntervalSet<Unit, Repr> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Unit, Repr: Ord + Copy> From<Interval<Unit, Repr>> for IntervalSet<Unit, Repr> {
+    fn from(interval: Interval<Unit, Repr>) -> Self {
+        let mut result = Self::new();
+        result.insert(interval);
+        result
+    }
+}
+
+impl<Unit, Repr: Ord + Copy> FromIterator<Interval<Unit, Repr>> for IntervalSet<Unit, Repr> {
+    fn from_iter<I: IntoIterator<Item = Interval<Unit, Repr>>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<Unit, Repr: Ord + Copy> Extend<Interval<Unit, Repr>> for IntervalSet<Unit, Repr> {
+    fn extend<I: IntoIterator<Item = Interval<Unit, Repr>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<'a, Unit, Repr> IntoIterator for &'a IntervalSet<Unit, Repr> {
+    type Item = &'a Interval<Unit, Repr>;
+    type IntoIter = std::slice::Iter<'a, Interval<Unit, Repr>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<Unit, Repr: Copy> Clone for IntervalSet<Unit, Repr> {
+    fn clone(&self) -> Self {
+        Self {
+            intervals: self.intervals.clone(),
+        }
+    }
+}
+
+impl<Unit, Repr: PartialEq> PartialEq for IntervalSet<Unit, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.intervals.eq(&rhs.intervals)
+    }
+}
+
+impl<Unit, Repr: Eq> Eq for IntervalSet<Unit, Repr> {}
+
+impl<Unit, Repr: Hash> Hash for IntervalSet<Unit, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.intervals.hash(state)
+    }
+}
+
+impl<Unit, Repr: fmt::Debug> fmt::Debug for IntervalSet<Unit, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.intervals.iter()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Unit, Repr: Serialize> Serialize for IntervalSet<Unit, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.intervals.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Unit, Repr> Deserialize<'de> for IntervalSet<Unit, Repr>
+where
+    Repr: Deserialize<'de> + Ord + Copy,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Interval<Unit, Repr>>::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Minutes {}
+    type Set = IntervalSet<Minutes, u32>;
+
+    fn set(intervals: &[(u32, u32)]) -> Set {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(Instant::from(s), Instant::from(e)).unwrap())
+            .collect()
+    }
+
+    fn bounds(set: &Set) -> Vec<(u32, u32)> {
+        set.iter()
+            .map(|x| (x.start().get(), x.end().get()))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_merges_overlapping_and_adjacent() {
+        assert_eq!(bounds(&set(&[(1, 3), (5, 7), (3, 5)])), vec![(1, 7)]);
+        assert_eq!(
+            bounds(&set(&[(5, 7), (1, 3), (2, 4)])),
+            vec![(1, 4), (5, 7)]
+        );
+        assert_eq!(bounds(&set(&[(1, 10), (2, 3), (4, 4)])), vec![(1, 10)]);
+        assert_eq!(bounds(&set(&[(4, 4)])), vec![]);
+    }
+
+    #[test]
+    fn test_remove_splits_intervals() {
+        let mut s = set(&[(1, 10), (20, 30)]);
+        s.remove(Interval::new(Instant::from(5), Instant::from(25)).unwrap());
+        assert_eq!(bounds(&s), vec![(1, 5), (25, 30)]);
+        s.remove(Interval::new(Instant::from(2), Instant::from(3)).unwrap());
+        assert_eq!(bounds(&s), vec![(1, 2), (3, 5), (25, 30)]);
+        s.remove(Interval::new(Instant::from(0), Instant::from(40)).unwrap());
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 5), (10, 15), (20, 25)]);
+        let b = set(&[(3, 12), (24, 30)]);
+
+        assert_eq!(bounds(&a.union(&b)), vec![(0, 15), (20, 30)]);
+        assert_eq!(
+            bounds(&a.intersection(&b)),
+            vec![(3, 5), (10, 12), (24, 25)]
+        );
+        assert_eq!(bounds(&a.difference(&b)), vec![(0, 3), (12, 15), (20, 24)]);
+        assert_eq!(bounds(&b.difference(&a)), vec![(5, 10), (25, 30)]);
+    }
+}
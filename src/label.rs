@@ -0,0 +1,217 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::displayer::{DisplayProxy, DisplayerOf};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// `Label<Kind, Repr>` provides a type-safe way to keep string
+/// identifiers: names, slugs, tags, etc. By default labels are backed
+/// by `Arc<str>`, so cloning a label doesn't allocate. Any `Repr` that
+/// can be borrowed as `str` will do, e.g. `Box<str>`, `String` or
+/// `&'static str`.
+///
+/// Labels can be looked up in maps by `&str`:
+///
+/// ```
+/// use phantom_newtype::Label;
+/// use std::collections::HashMap;
+///
+/// enum Service {}
+/// type ServiceName = Label<Service>;
+///
+/// let mut ports = HashMap::new();
+/// ports.insert("frontend".parse::<ServiceName>().unwrap(), 8080);
+///
+/// assert_eq!(ports.get("frontend"), Some(&8080));
+/// ```
+///
+/// Labels of different kinds can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::Label;
+///
+/// enum Service {}
+/// enum Region {}
+///
+/// let service: Label<Service> = "eu-west".parse().unwrap();
+/// let region: Label<Region> = "eu-west".parse().unwrap();
+/// assert_eq!(service, region);
+/// ```
+///
+/// Labels backed by `&'static str` can be defined as constants:
+///
+/// ```
+/// use phantom_newtype::Label;
+///
+/// enum Role {}
+/// const ADMIN: Label<Role, &'static str> = Label::new("admin");
+///
+/// assert_eq!(ADMIN.as_str(), "admin");
+/// assert_eq!(format!("{} {:?}", ADMIN, ADMIN), r#"admin "admin""#);
+/// ```
+///
+/// Labels are serialized as strings.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Label;
+/// use serde_json;
+///
+/// enum Service {}
+/// type ServiceName = Label<Service>;
+///
+/// let name: ServiceName = serde_json::from_str(r#""frontend""#).unwrap();
+/// assert_eq!(name.as_str(), "frontend");
+/// assert_eq!(serde_json::to_string(&name).unwrap(), r#""frontend""#);
+/// }
+/// ```
+pub struct Label<Kind, Repr = Arc<str>>(Repr, PhantomData<std::sync::Mutex<Kind>>);
+
+impl<Kind, Repr> Label<Kind, Repr> {
+    /// `new` is a synonym for `from` that can be evaluated in
+    /// compile time. The main use-case of this functions is defining
+    /// constants.
+    pub const fn new(repr: Repr) -> Label<Kind, Repr> {
+        Label(repr, PhantomData)
+    }
+
+    /// Returns the underlying representation of the label.
+    pub const fn get(&self) -> &Repr {
+        &self.0
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> Label<Kind, Repr> {
+    /// Returns the label as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.borrow()
+    }
+}
+
+impl<Kind, Repr> Label<Kind, Repr>
+where
+    Kind: DisplayerOf<Label<Kind, Repr>>,
+{
+    /// `display` provides a machanism to implement a custom display
+    /// for phantom types.
+    pub fn display(&self) -> DisplayProxy<'_, Self, Kind> {
+        DisplayProxy::new(self)
+    }
+}
+
+impl<Kind, Repr> From<Repr> for Label<Kind, Repr> {
+    fn from(repr: Repr) -> Self {
+        Self::new(repr)
+    }
+}
+
+impl<Kind, Repr> FromStr for Label<Kind, Repr>
+where
+    Repr: for<'a> From<&'a str>,
+{
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(Self::new(Repr::from(s)))
+    }
+}
+
+// `Borrow<str>` requires `Eq`, `Ord` and `Hash` of the label to agree
+// with the ones of `str`, that's why all the comparisons below go
+// through `as_str`.
+impl<Kind, Repr: Borrow<str>> Borrow<str> for Label<Kind, Repr> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> AsRef<str> for Label<Kind, Repr> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Kind, Repr: Clone> Clone for Label<Kind, Repr> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<Kind, Repr: Copy> Copy for Label<Kind, Repr> {}
+
+impl<Kind, Repr: Borrow<str>> PartialEq for Label<Kind, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.as_str().eq(rhs.as_str())
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> Eq for Label<Kind, Repr> {}
+
+impl<Kind, Repr: Borrow<str>> PartialOrd for Label<Kind, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> Ord for Label<Kind, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.as_str().cmp(rhs.as_str())
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> Hash for Label<Kind, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> fmt::Debug for Label<Kind, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl<Kind, Repr: Borrow<str>> fmt::Display for Label<Kind, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Kind, Repr: Borrow<str>> Serialize for Label<Kind, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+// Going through `String` allows deserializing reprs like `Arc<str>`
+// without enabling the `rc` feature of `serde`.
+#[cfg(feature = "serde")]
+impl<'de, Kind, Repr> Deserialize<'de> for Label<Kind, Repr>
+where
+    Repr: for<'a> From<&'a str>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|s| Self::new(Repr::from(s.as_str())))
+    }
+}
@@ -13,11 +13,63 @@
 // limitations under the License.
 
 mod amount;
+mod bounded;
+mod digest;
 mod displayer;
+mod flags;
+mod fraction;
 mod id;
+mod id_generator;
+mod id_vec;
 mod instant;
+mod integer;
+mod interval;
+mod interval_set;
+mod label;
+mod money;
+mod non_zero;
+#[cfg(feature = "num-traits")]
+mod num;
+mod point;
+mod policy;
+mod rate;
+mod ratio;
+mod retag;
+mod rounding;
+mod sealed;
+#[cfg(feature = "secret")]
+mod secret;
+mod sequence;
+mod tagged;
+mod units;
 
 pub use amount::Amount;
+pub use bounded::{Bounded, BoundedRepr};
+pub use digest::{Digest, HashAlgorithm, ParseDigestError};
 pub use displayer::{DisplayProxy, DisplayerOf};
+pub use flags::{BitsRepr, Flags, NamedFlags};
+pub use fraction::{BasisPoints, Fraction, FractionScale, PartsPerMillion, Percent};
 pub use id::Id;
-pub use instant::Instant;
+pub use id_generator::{AtomicRepr, IdGenerator};
+pub use id_vec::{IdSlice, IdVec, IndexRepr};
+pub use instant::{Instant, InstantSub};
+pub use integer::{IntegerRepr, OffsetRepr};
+pub use interval::{Closed, HalfOpen, Interval, IntervalBounds};
+pub use interval_set::IntervalSet;
+pub use label::Label;
+pub use money::{Currency, Money, ParseMoneyError};
+pub use non_zero::{NonZeroAmount, NonZeroRepr, PositiveAmount, PositiveRepr};
+pub use point::{Displacement, Point};
+pub use policy::{
+    ArithmeticPolicy, Checked, OverflowPolicy, PanicOnOverflow, PolicyAmount, PolicyInstant,
+    Saturate, Wrap,
+};
+pub use rate::Rate;
+pub use ratio::UnitRatio;
+pub use retag::Retag;
+pub use rounding::Rounding;
+#[cfg(feature = "secret")]
+pub use secret::{Secret, SerializableSecret};
+pub use sequence::{Sequence, SerialRepr};
+pub use tagged::Tagged;
+pub use units::{BaseUnit, Per, Times, UnitDiv, UnitMul};
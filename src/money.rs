@@ -0,0 +1,544 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::rounding::{div_round, from_i128, to_i128, to_sign_magnitude, Rounding};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A marker trait describing a currency of
+/// [`Money`](struct.Money.html).
+///
+/// ```
+/// use phantom_newtype::Currency;
+///
+/// enum Usd {}
+///
+/// impl Currency for Usd {
+///     const CODE: &'static str = "USD";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+/// ```
+///
+/// `10^MINOR_DIGITS` must fit into `u128`:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Currency, Money};
+///
+/// enum Dust {}
+///
+/// impl Currency for Dust {
+///     const CODE: &'static str = "DST";
+///     const MINOR_DIGITS: u32 = 39;
+/// }
+///
+/// let _ = Money::<Dust, u128>::new(1).to_string();
+/// ```
+pub trait Currency {
+    /// ISO 4217 code of the currency, e.g. "USD".
+    const CODE: &'static str;
+
+    /// The number of decimal digits of the minor unit, e.g. 2 for USD
+    /// (cents) and 0 for JPY. Formatting and parsing money of
+    /// currencies with more than 38 minor digits fails to compile.
+    const MINOR_DIGITS: u32;
+}
+
+/// `Money<Currency, Repr>` is an amount of money in the minor units
+/// (e.g. cents) of the `Currency`.
+///
+/// Money is formatted and parsed according to the number of minor
+/// digits of the currency:
+///
+/// ```
+/// use phantom_newtype::{Currency, Money};
+///
+/// enum Usd {}
+/// impl Currency for Usd {
+///     const CODE: &'static str = "USD";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+///
+/// enum Jpy {}
+/// impl Currency for Jpy {
+///     const CODE: &'static str = "JPY";
+///     const MINOR_DIGITS: u32 = 0;
+/// }
+///
+/// assert_eq!(Money::<Usd, i64>::new(1234).to_string(), "12.34 USD");
+/// assert_eq!(Money::<Usd, i64>::new(-5).to_string(), "-0.05 USD");
+/// assert_eq!(Money::<Jpy, u32>::new(1234).to_string(), "1234 JPY");
+///
+/// assert_eq!("12.34 USD".parse(), Ok(Money::<Usd, i64>::new(1234)));
+/// assert_eq!("12.3 USD".parse(), Ok(Money::<Usd, i64>::new(1230)));
+/// assert!("12.34 JPY".parse::<Money<Jpy, u32>>().is_err());
+/// assert!("12 USD".parse::<Money<Jpy, u32>>().is_err());
+/// ```
+///
+/// Division by a scalar uses banker's rounding, `allocate` splits money
+/// without losing a single minor unit:
+///
+/// ```
+/// use phantom_newtype::{Currency, Money, Rounding};
+///
+/// enum Usd {}
+/// impl Currency for Usd {
+///     const CODE: &'static str = "USD";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+/// type Dollars = Money<Usd, i64>;
+///
+/// assert_eq!(Dollars::new(10) / 4, Dollars::new(2));
+/// assert_eq!(Dollars::new(14) / 4, Dollars::new(4));
+/// assert_eq!(Dollars::new(10).div_rounded(4, Rounding::Ceil), Dollars::new(3));
+///
+/// assert_eq!(
+///     Dollars::new(10000).allocate(&[1, 1, 1]),
+///     vec![Dollars::new(3334), Dollars::new(3333), Dollars::new(3333)]
+/// );
+/// assert_eq!(
+///     Dollars::new(5).allocate(&[3, 7]),
+///     vec![Dollars::new(2), Dollars::new(3)]
+/// );
+/// ```
+///
+/// Money in different currencies can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Currency, Money};
+///
+/// enum Usd {}
+/// impl Currency for Usd {
+///     const CODE: &'static str = "USD";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+///
+/// enum Eur {}
+/// impl Currency for Eur {
+///     const CODE: &'static str = "EUR";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+///
+/// let total = Money::<Usd, i64>::new(100) + Money::<Eur, i64>::new(100);
+/// ```
+///
+/// Money is serialized as the number of minor units.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{Currency, Money};
+/// use serde_json;
+///
+/// enum Usd {}
+/// impl Currency for Usd {
+///     const CODE: &'static str = "USD";
+///     const MINOR_DIGITS: u32 = 2;
+/// }
+/// type Dollars = Money<Usd, i64>;
+///
+/// assert_eq!(serde_json::to_string(&Dollars::new(1234)).unwrap(), "1234");
+/// assert_eq!(serde_json::from_str::<Dollars>("1234").unwrap(), Dollars::new(1234));
+/// }
+/// ```
+pub struct Money<C, Repr>(Repr, PhantomData<std::sync::Mutex<C>>);
+
+impl<C, Repr> Money<C, Repr> {
+    /// Constructs money from the number of minor units. This function
+    /// can be evaluated in compile time.
+    pub const fn new(minor_units: Repr) -> Money<C, Repr> {
+        Money(minor_units, PhantomData)
+    }
+}
+
+impl<C: Currency, Repr> Money<C, Repr> {
+    // `10^MINOR_DIGITS` must fit into `u128`. Formatting and parsing
+    // refer to this constant to fail the compilation otherwise.
+    const VALID_MINOR_DIGITS: () = assert!(
+        C::MINOR_DIGITS <= 38,
+        "currencies can't have more than 38 minor digits"
+    );
+}
+
+impl<C, Repr: Copy> Money<C, Repr> {
+    /// Returns the number of minor units.
+    pub fn minor_units(&self) -> Repr {
+        self.0
+    }
+
+    /// Returns the number of minor units as an amount.
+    pub fn to_amount(&self) -> Amount<C, Repr> {
+        Amount::new(self.0)
+    }
+}
+
+impl<C, Repr> Money<C, Repr>
+where
+    C: Currency,
+    Repr: TryFrom<i128> + TryInto<i128> + Copy,
+{
+    /// Divides money by a scalar rounding the result according to
+    /// `rounding`.
+    pub fn div_rounded(self, divisor: Repr, rounding: Rounding) -> Self {
+        Self::new(from_i128(div_round(
+            to_i128(self.0),
+            to_i128(divisor),
+            rounding,
+        )))
+    }
+
+    /// Splits money into parts proportional to `weights`. The parts
+    /// always add up to the original value, the minor units left after
+    /// the proportional split go to the parts with the largest
+    /// remainders.
+    ///
+    /// Panics if all the weights are zero.
+    pub fn allocate(&self, weights: &[u32]) -> Vec<Self> {
+        let total = to_i128(self.0);
+        let weight_sum: i128 = weights.iter().map(|w| i128::from(*w)).sum();
+        assert!(weight_sum > 0, "weights must not all be zero");
+
+        let mut shares = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+        for (i, w) in weights.iter().enumerate() {
+            let product = total
+                .checked_mul(i128::from(*w))
+                .expect("attempt to multiply with overflow");
+            shares.push(product.div_euclid(weight_sum));
+            remainders.push((product.rem_euclid(weight_sum), i));
+        }
+
+        // Shares are rounded down, so the leftover is non-negative and
+        // smaller than the number of non-zero remainders.
+        let leftover = total - shares.iter().sum::<i128>();
+        remainders.sort_by(|(r1, i1), (r2, i2)| r2.cmp(r1).then(i1.cmp(i2)));
+        for (_, i) in remainders.into_iter().take(leftover as usize) {
+            shares[i] += 1;
+        }
+
+        shares
+            .into_iter()
+            .map(|share| Self::new(from_i128(share)))
+            .collect()
+    }
+}
+
+/// An error returned when parsing [`Money`](struct.Money.html) fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMoneyError {
+    /// The input doesn't look like "12.34 USD".
+    InvalidFormat,
+    /// The currency code doesn't match the expected currency.
+    CurrencyMismatch,
+    /// The number has more decimal places than the currency allows.
+    TooManyDecimals,
+    /// The number doesn't fit into the representation.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseMoneyError::InvalidFormat => "invalid money format",
+            ParseMoneyError::CurrencyMismatch => "unexpected currency code",
+            ParseMoneyError::TooManyDecimals => "too many decimal places",
+            ParseMoneyError::OutOfRange => "value is out of range",
+        })
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+impl<C, Repr> FromStr for Money<C, Repr>
+where
+    C: Currency,
+    Repr: TryFrom<i128>,
+{
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, ParseMoneyError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_MINOR_DIGITS;
+        let mut words = s.split_whitespace();
+        let (number, code) = match (words.next(), words.next(), words.next()) {
+            (Some(number), Some(code), None) => (number, code),
+            _ => return Err(ParseMoneyError::InvalidFormat),
+        };
+        if code != C::CODE {
+            return Err(ParseMoneyError::CurrencyMismatch);
+        }
+
+        let (negative, number) = match number.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, number),
+        };
+        let (int_part, frac_part) = match number.split_once('.') {
+            Some((int_part, frac_part)) if !frac_part.is_empty() => (int_part, frac_part),
+            Some(_) => return Err(ParseMoneyError::InvalidFormat),
+            None => (number, ""),
+        };
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if int_part.is_empty() || !is_digits(int_part) || !is_digits(frac_part) {
+            return Err(ParseMoneyError::InvalidFormat);
+        }
+        if frac_part.len() > C::MINOR_DIGITS as usize {
+            return Err(ParseMoneyError::TooManyDecimals);
+        }
+
+        let padding = C::MINOR_DIGITS as usize - frac_part.len();
+        let minor_units = int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .chain(std::iter::repeat(b'0').take(padding))
+            .try_fold(0i128, |acc, digit| {
+                acc.checked_mul(10)?.checked_add(i128::from(digit - b'0'))
+            })
+            .ok_or(ParseMoneyError::OutOfRange)?;
+        let minor_units = if negative { -minor_units } else { minor_units };
+
+        Repr::try_from(minor_units)
+            .map(Self::new)
+            .map_err(|_| ParseMoneyError::OutOfRange)
+    }
+}
+
+impl<C, Repr: Copy> From<Amount<C, Repr>> for Money<C, Repr> {
+    fn from(amount: Amount<C, Repr>) -> Self {
+        Self::new(amount.get())
+    }
+}
+
+impl<C, Repr: Copy> Clone for Money<C, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, Repr: Copy> Copy for Money<C, Repr> {}
+
+impl<C, Repr: PartialEq> PartialEq for Money<C, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<C, Repr: Eq> Eq for Money<C, Repr> {}
+
+impl<C, Repr: PartialOrd> PartialOrd for Money<C, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<C, Repr: Ord> Ord for Money<C, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<C, Repr: Hash> Hash for Money<C, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<C, Repr> Add for Money<C, Repr>
+where
+    Repr: AddAssign + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<C, Repr> AddAssign for Money<C, Repr>
+where
+    Repr: AddAssign + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0
+    }
+}
+
+impl<C, Repr> Sub for Money<C, Repr>
+where
+    Repr: SubAssign + Copy,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self.sub_assign(rhs);
+        self
+    }
+}
+
+impl<C, Repr> SubAssign for Money<C, Repr>
+where
+    Repr: SubAssign + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0
+    }
+}
+
+impl<C, Repr> Mul<Repr> for Money<C, Repr>
+where
+    Repr: MulAssign + Copy,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: Repr) -> Self {
+        self.mul_assign(rhs);
+        self
+    }
+}
+
+impl<C, Repr> MulAssign<Repr> for Money<C, Repr>
+where
+    Repr: MulAssign + Copy,
+{
+    fn mul_assign(&mut self, rhs: Repr) {
+        self.0 *= rhs
+    }
+}
+
+impl<C, Repr> Div<Repr> for Money<C, Repr>
+where
+    C: Currency,
+    Repr: TryFrom<i128> + TryInto<i128> + Copy,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Repr) -> Self {
+        self.div_rounded(rhs, Rounding::HalfEven)
+    }
+}
+
+impl<C, Repr> fmt::Debug for Money<C, Repr>
+where
+    C: Currency,
+    Repr: TryInto<i128> + TryInto<u128> + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<C, Repr> fmt::Display for Money<C, Repr>
+where
+    C: Currency,
+    Repr: TryInto<i128> + TryInto<u128> + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_MINOR_DIGITS;
+        let (negative, abs) = to_sign_magnitude(self.0);
+        let scale = 10u128.pow(C::MINOR_DIGITS);
+        if negative {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", abs / scale)?;
+        if C::MINOR_DIGITS > 0 {
+            write!(
+                f,
+                ".{:0width$}",
+                abs % scale,
+                width = C::MINOR_DIGITS as usize
+            )?;
+        }
+        write!(f, " {}", C::CODE)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C, Repr: Serialize> Serialize for Money<C, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C, Repr> Deserialize<'de> for Money<C, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Money::<C, Repr>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Usd {}
+
+    impl Currency for Usd {
+        const CODE: &'static str = "USD";
+        const MINOR_DIGITS: u32 = 2;
+    }
+
+    type Dollars = Money<Usd, i64>;
+
+    #[test]
+    fn test_parse() {
+        let parse = |s: &str| s.parse::<Dollars>();
+
+        assert_eq!(parse("0.01 USD"), Ok(Dollars::new(1)));
+        assert_eq!(parse("-12 USD"), Ok(Dollars::new(-1200)));
+        assert_eq!(parse("  7.5   USD "), Ok(Dollars::new(750)));
+        assert_eq!(parse("12.34"), Err(ParseMoneyError::InvalidFormat));
+        assert_eq!(parse(".5 USD"), Err(ParseMoneyError::InvalidFormat));
+        assert_eq!(parse("5. USD"), Err(ParseMoneyError::InvalidFormat));
+        assert_eq!(parse("1e3 USD"), Err(ParseMoneyError::InvalidFormat));
+        assert_eq!(parse("1.00 EUR"), Err(ParseMoneyError::CurrencyMismatch));
+        assert_eq!(parse("1.001 USD"), Err(ParseMoneyError::TooManyDecimals));
+        assert_eq!(
+            parse("92233720368547758.08 USD"),
+            Err(ParseMoneyError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_display_at_repr_edges() {
+        let max = Money::<Usd, u128>::new(u128::MAX);
+        assert_eq!(
+            max.to_string(),
+            "3402823669209384634633746074317682114.55 USD"
+        );
+        assert_eq!(format!("{:?}", max), max.to_string());
+        assert_eq!(
+            Money::<Usd, i128>::new(i128::MIN).to_string(),
+            "-1701411834604692317316873037158841057.28 USD"
+        );
+    }
+
+    #[test]
+    fn test_allocate_negative() {
+        let parts = Dollars::new(-100).allocate(&[1, 2, 0]);
+        assert_eq!(
+            parts,
+            vec![Dollars::new(-33), Dollars::new(-67), Dollars::new(0)]
+        );
+    }
+}
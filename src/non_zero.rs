@@ -0,0 +1,394 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::sealed::Sealed;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+
+/// Representations of [`NonZeroAmount`](struct.NonZeroAmount.html),
+/// implemented by the `std::num::NonZero*` types. This trait is sealed
+/// and can't be implemented outside of this crate.
+pub trait NonZeroRepr: Copy + Sealed {
+    /// The primitive type of the same size.
+    type Primitive: Copy;
+
+    /// Returns `None` if `repr` is zero.
+    fn new(repr: Self::Primitive) -> Option<Self>;
+
+    /// Returns the primitive value.
+    fn get(self) -> Self::Primitive;
+
+    /// Adds two values, returns `None` on overflow or if the result is
+    /// zero.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Multiplies two values, returns `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+/// Representations of [`NonZeroAmount`](struct.NonZeroAmount.html)
+/// that are strictly positive, implemented by the
+/// `std::num::NonZeroU*` types. This trait is sealed and can't be
+/// implemented outside of this crate, so that signed amounts can't be
+/// added:
+///
+/// ```compile_fail
+/// use phantom_newtype::{NonZeroRepr, PositiveRepr};
+/// use std::num::NonZeroI64;
+///
+/// #[derive(Clone, Copy)]
+/// struct Signed(NonZeroI64);
+///
+/// impl NonZeroRepr for Signed {
+///     type Primitive = i64;
+///
+///     fn new(repr: i64) -> Option<Self> {
+///         NonZeroI64::new(repr).map(Signed)
+///     }
+///
+///     fn get(self) -> i64 {
+///         self.0.get()
+///     }
+///
+///     fn checked_add(self, rhs: Self) -> Option<Self> {
+///         Self::new(self.get().checked_add(rhs.get())?)
+///     }
+///
+///     fn checked_mul(self, rhs: Self) -> Option<Self> {
+///         Self::new(self.get().checked_mul(rhs.get())?)
+///     }
+/// }
+///
+/// impl PositiveRepr for Signed {}
+/// ```
+pub trait PositiveRepr: NonZeroRepr + Sealed {}
+
+macro_rules! impl_non_zero_repr {
+    ($($non_zero:ident($primitive:ident)),*) => {
+        $(
+            impl NonZeroRepr for $non_zero {
+                type Primitive = $primitive;
+
+                fn new(repr: $primitive) -> Option<Self> {
+                    $non_zero::new(repr)
+                }
+
+                fn get(self) -> $primitive {
+                    $non_zero::get(self)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    self.get().checked_add(rhs.get()).and_then($non_zero::new)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    self.get().checked_mul(rhs.get()).and_then($non_zero::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_repr!(
+    NonZeroU8(u8),
+    NonZeroU16(u16),
+    NonZeroU32(u32),
+    NonZeroU64(u64),
+    NonZeroU128(u128),
+    NonZeroUsize(usize),
+    NonZeroI8(i8),
+    NonZeroI16(i16),
+    NonZeroI32(i32),
+    NonZeroI64(i64),
+    NonZeroI128(i128),
+    NonZeroIsize(isize)
+);
+
+impl PositiveRepr for NonZeroU8 {}
+impl PositiveRepr for NonZeroU16 {}
+impl PositiveRepr for NonZeroU32 {}
+impl PositiveRepr for NonZeroU64 {}
+impl PositiveRepr for NonZeroU128 {}
+impl PositiveRepr for NonZeroUsize {}
+
+/// `NonZeroAmount<Unit, Repr>` is an amount of some `Unit` that is
+/// guaranteed not to be zero. `Repr` is one of the `std::num::NonZero*`
+/// types, amounts using `NonZeroU*` representations are strictly
+/// positive.
+///
+/// Like `Option<NonZeroU64>`, an optional non-zero amount takes no
+/// extra space:
+///
+/// ```
+/// use phantom_newtype::NonZeroAmount;
+/// use std::num::NonZeroU64;
+///
+/// enum Bytes {}
+/// type BlockSize = NonZeroAmount<Bytes, NonZeroU64>;
+///
+/// assert_eq!(std::mem::size_of::<Option<BlockSize>>(), std::mem::size_of::<u64>());
+/// ```
+///
+/// Non-zero amounts are converted from and to regular amounts:
+///
+/// ```
+/// use phantom_newtype::{Amount, NonZeroAmount};
+/// use std::num::NonZeroU64;
+///
+/// enum Bytes {}
+/// type BlockSize = NonZeroAmount<Bytes, NonZeroU64>;
+///
+/// let size = BlockSize::from_amount(Amount::from(4096)).unwrap();
+/// assert_eq!(size.to_amount(), Amount::<Bytes, u64>::from(4096));
+/// assert_eq!(BlockSize::from_amount(Amount::from(0)), None);
+/// ```
+///
+/// Arithmetic is only provided where it preserves the invariant:
+/// positive amounts can be added, all non-zero amounts can be scaled
+/// by a non-zero scalar. Operators panic on overflow.
+///
+/// ```
+/// use phantom_newtype::NonZeroAmount;
+/// use std::num::{NonZeroI64, NonZeroU64};
+///
+/// enum Bytes {}
+/// type BlockSize = NonZeroAmount<Bytes, NonZeroU64>;
+///
+/// let size = BlockSize::new(NonZeroU64::new(512).unwrap());
+/// let three = NonZeroU64::new(3).unwrap();
+///
+/// assert_eq!((size + size).get().get(), 1024);
+/// assert_eq!((size * three).get().get(), 1536);
+///
+/// enum Cents {}
+/// type Delta = NonZeroAmount<Cents, NonZeroI64>;
+///
+/// let delta = Delta::new(NonZeroI64::new(-5).unwrap());
+/// assert_eq!(delta.checked_add(Delta::new(NonZeroI64::new(5).unwrap())), None);
+/// assert_eq!((delta * NonZeroI64::new(-2).unwrap()).get().get(), 10);
+/// ```
+///
+/// Signed non-zero amounts can't be added with `+`, the sum might be
+/// zero:
+///
+/// ```compile_fail
+/// use phantom_newtype::NonZeroAmount;
+/// use std::num::NonZeroI64;
+///
+/// enum Cents {}
+/// type Delta = NonZeroAmount<Cents, NonZeroI64>;
+///
+/// let delta = Delta::new(NonZeroI64::new(-5).unwrap());
+/// let _ = delta + delta;
+/// ```
+///
+/// Non-zero amounts are serialized as their primitive values,
+/// deserializing a zero fails.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::NonZeroAmount;
+/// use serde_json;
+/// use std::num::NonZeroU64;
+///
+/// enum Bytes {}
+/// type BlockSize = NonZeroAmount<Bytes, NonZeroU64>;
+///
+/// let size: BlockSize = serde_json::from_str("4096").unwrap();
+/// assert_eq!(serde_json::to_string(&size).unwrap(), "4096");
+/// assert!(serde_json::from_str::<BlockSize>("0").is_err());
+/// }
+/// ```
+pub struct NonZeroAmount<Unit, Repr>(Repr, PhantomData<std::sync::Mutex<Unit>>);
+
+/// `PositiveAmount<Unit, Repr>` is a strictly positive amount of some
+/// `Unit`, `Repr` is expected to be one of the `NonZeroU*` types. It's
+/// an alias of [`NonZeroAmount`](struct.NonZeroAmount.html): the
+/// compiler doesn't check bounds of type aliases, the operations that
+/// rely on positivity require `Repr: PositiveRepr` instead.
+///
+/// ```
+/// use phantom_newtype::PositiveAmount;
+/// use std::num::NonZeroU64;
+///
+/// enum Bytes {}
+/// type BlockSize = PositiveAmount<Bytes, NonZeroU64>;
+///
+/// let size = BlockSize::new(NonZeroU64::new(512).unwrap());
+/// assert_eq!((size + size).get().get(), 1024);
+/// ```
+pub type PositiveAmount<Unit, Repr> = NonZeroAmount<Unit, Repr>;
+
+impl<Unit, Repr> NonZeroAmount<Unit, Repr> {
+    /// `new` can be evaluated in compile time. The main use-case of
+    /// this functions is defining constants.
+    pub const fn new(repr: Repr) -> NonZeroAmount<Unit, Repr> {
+        NonZeroAmount(repr, PhantomData)
+    }
+}
+
+impl<Unit, Repr: NonZeroRepr> NonZeroAmount<Unit, Repr> {
+    /// Returns the wrapped value.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+
+    /// Converts an amount into a non-zero amount, returns `None` if
+    /// the amount is zero.
+    pub fn from_amount(amount: Amount<Unit, Repr::Primitive>) -> Option<Self> {
+        Repr::new(amount.get()).map(Self::new)
+    }
+
+    /// Converts a non-zero amount into a regular amount.
+    pub fn to_amount(&self) -> Amount<Unit, Repr::Primitive> {
+        Amount::new(self.0.get())
+    }
+
+    /// Adds two amounts, returns `None` on overflow or if the result
+    /// is zero.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self::new)
+    }
+
+    /// Scales an amount, returns `None` on overflow.
+    pub fn checked_mul(self, rhs: Repr) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self::new)
+    }
+}
+
+impl<Unit, Repr: Copy> Clone for NonZeroAmount<Unit, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Unit, Repr: Copy> Copy for NonZeroAmount<Unit, Repr> {}
+
+impl<Unit, Repr: PartialEq> PartialEq for NonZeroAmount<Unit, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Eq> Eq for NonZeroAmount<Unit, Repr> {}
+
+impl<Unit, Repr: PartialOrd> PartialOrd for NonZeroAmount<Unit, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Ord> Ord for NonZeroAmount<Unit, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Hash> Hash for NonZeroAmount<Unit, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Unit, Repr> Add for NonZeroAmount<Unit, Repr>
+where
+    Repr: PositiveRepr,
+{
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<Unit, Repr> AddAssign for NonZeroAmount<Unit, Repr>
+where
+    Repr: PositiveRepr,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = self
+            .0
+            .checked_add(rhs.0)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl<Unit, Repr> MulAssign<Repr> for NonZeroAmount<Unit, Repr>
+where
+    Repr: NonZeroRepr,
+{
+    fn mul_assign(&mut self, rhs: Repr) {
+        self.0 = self
+            .0
+            .checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl<Unit, Repr> Mul<Repr> for NonZeroAmount<Unit, Repr>
+where
+    Repr: NonZeroRepr,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: Repr) -> Self {
+        self.mul_assign(rhs);
+        self
+    }
+}
+
+impl<Unit, Repr> fmt::Debug for NonZeroAmount<Unit, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Unit, Repr> fmt::Display for NonZeroAmount<Unit, Repr>
+where
+    Repr: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Unit, Repr: Serialize> Serialize for NonZeroAmount<Unit, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Unit, Repr> Deserialize<'de> for NonZeroAmount<Unit, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(NonZeroAmount::<Unit, Repr>::new)
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of [`num-traits`](https://docs.rs/num-traits)
+//! traits, enabled by the `num-traits` feature.
+//!
+//! Only the traits that fit the archetypes are implemented. `One`,
+//! `Num`, `Signed` and the `*Mul` traits require multiplying values of
+//! the same type, but the product of two amounts has a different unit,
+//! and `Instant` only supports the traits that don't require adding
+//! instants.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use num_traits::{
+    Bounded, CheckedAdd, CheckedSub, FromPrimitive, SaturatingAdd, SaturatingSub, ToPrimitive, Zero,
+};
+use std::ops::{AddAssign, SubAssign};
+
+impl<Unit, Repr> Zero for Amount<Unit, Repr>
+where
+    Repr: Zero + AddAssign + Copy,
+{
+    fn zero() -> Self {
+        Self::new(Repr::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.get().is_zero()
+    }
+}
+
+impl<Unit, Repr> CheckedAdd for Amount<Unit, Repr>
+where
+    Repr: CheckedAdd + AddAssign + Copy,
+{
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        self.get().checked_add(&v.get()).map(Self::new)
+    }
+}
+
+impl<Unit, Repr> CheckedSub for Amount<Unit, Repr>
+where
+    Repr: CheckedSub + SubAssign + Copy,
+{
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        self.get().checked_sub(&v.get()).map(Self::new)
+    }
+}
+
+impl<Unit, Repr> SaturatingAdd for Amount<Unit, Repr>
+where
+    Repr: SaturatingAdd + AddAssign + Copy,
+{
+    fn saturating_add(&self, v: &Self) -> Self {
+        Self::new(self.get().saturating_add(&v.get()))
+    }
+}
+
+impl<Unit, Repr> SaturatingSub for Amount<Unit, Repr>
+where
+    Repr: SaturatingSub + SubAssign + Copy,
+{
+    fn saturating_sub(&self, v: &Self) -> Self {
+        Self::new(self.get().saturating_sub(&v.get()))
+    }
+}
+
+macro_rules! impl_bounded_and_primitive {
+    ($($archetype:ident),*) => {
+        $(
+            impl<Unit, Repr: Bounded> Bounded for $archetype<Unit, Repr> {
+                fn min_value() -> Self {
+                    Self::new(Repr::min_value())
+                }
+
+                fn max_value() -> Self {
+                    Self::new(Repr::max_value())
+                }
+            }
+
+            impl<Unit, Repr: ToPrimitive + Copy> ToPrimitive for $archetype<Unit, Repr> {
+                fn to_i64(&self) -> Option<i64> {
+                    self.get().to_i64()
+                }
+
+                fn to_u64(&self) -> Option<u64> {
+                    self.get().to_u64()
+                }
+
+                fn to_i128(&self) -> Option<i128> {
+                    self.get().to_i128()
+                }
+
+                fn to_u128(&self) -> Option<u128> {
+                    self.get().to_u128()
+                }
+
+                fn to_f64(&self) -> Option<f64> {
+                    self.get().to_f64()
+                }
+            }
+
+            impl<Unit, Repr: FromPrimitive> FromPrimitive for $archetype<Unit, Repr> {
+                fn from_i64(n: i64) -> Option<Self> {
+                    Repr::from_i64(n).map(Self::new)
+                }
+
+                fn from_u64(n: u64) -> Option<Self> {
+                    Repr::from_u64(n).map(Self::new)
+                }
+
+                fn from_i128(n: i128) -> Option<Self> {
+                    Repr::from_i128(n).map(Self::new)
+                }
+
+                fn from_u128(n: u128) -> Option<Self> {
+                    Repr::from_u128(n).map(Self::new)
+                }
+
+                fn from_f64(n: f64) -> Option<Self> {
+                    Repr::from_f64(n).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_and_primitive!(Amount, Instant);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Cycles {}
+    type CycleCount = Amount<Cycles, u32>;
+    type Tick = Instant<Cycles, u32>;
+
+    fn total<T: Zero + CheckedAdd + Copy>(xs: &[T]) -> Option<T> {
+        xs.iter().try_fold(T::zero(), |acc, x| acc.checked_add(x))
+    }
+
+    #[test]
+    fn test_generic_code() {
+        let xs = [CycleCount::from(1), CycleCount::from(2)];
+
+        assert_eq!(total(&xs), Some(CycleCount::from(3)));
+        assert_eq!(total(&[CycleCount::max_value(), xs[0]]), None);
+        assert_eq!(
+            SaturatingSub::saturating_sub(&xs[0], &xs[1]),
+            CycleCount::zero()
+        );
+    }
+
+    #[test]
+    fn test_primitive_conversions() {
+        assert_eq!(Tick::from_i64(-1), None);
+        assert_eq!(Tick::from_u64(5), Some(Tick::from(5)));
+        assert_eq!(Tick::max_value().to_u64(), Some(u64::from(u32::MAX)));
+        assert_eq!(CycleCount::from(7).to_f64(), Some(7.0));
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// `Point<Frame, [Repr; N]>` is a position in an N-dimensional space
+/// with the `Frame` reference frame (screen pixels, world
+/// coordinates, etc). Points are to
+/// [`Displacement`](struct.Displacement.html)s what
+/// [`Instant`](struct.Instant.html)s are to
+/// [`Amount`](struct.Amount.html)s:
+/// * Subtract a point from another point to get the displacement between them.
+/// * Add/subtract a displacement to/from a point to get another point.
+///
+/// ```
+/// use phantom_newtype::{Displacement, Point};
+///
+/// enum Screen {}
+/// type Pixel = Point<Screen, [i32; 2]>;
+/// type Offset = Displacement<Screen, [i32; 2]>;
+///
+/// let a = Pixel::new([10, 20]);
+/// let b = Pixel::new([13, 16]);
+///
+/// assert_eq!(b - a, Offset::new([3, -4]));
+/// assert_eq!(a + Offset::new([3, -4]), b);
+/// assert_eq!(b - Offset::new([3, -4]), a);
+/// ```
+///
+/// Points can't be added or scaled, and points in different frames
+/// can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::Point;
+///
+/// enum Screen {}
+/// enum World {}
+///
+/// let d = Point::<Screen, [f64; 2]>::new([1.0, 2.0]) - Point::<World, [f64; 2]>::new([1.0, 2.0]);
+/// ```
+///
+/// ```compile_fail
+/// use phantom_newtype::Point;
+///
+/// enum World {}
+/// type Position = Point<World, [f64; 3]>;
+///
+/// let p = Position::new([1.0, 2.0, 3.0]) + Position::new([1.0, 2.0, 3.0]);
+/// ```
+///
+/// Points are serialized as their coordinates.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Point;
+/// use serde_json;
+///
+/// enum World {}
+/// type Position = Point<World, [i64; 3]>;
+///
+/// let p = Position::new([1, 2, 3]);
+/// assert_eq!(serde_json::to_string(&p).unwrap(), "[1,2,3]");
+/// assert_eq!(serde_json::from_str::<Position>("[1,2,3]").unwrap(), p);
+/// }
+/// ```
+pub struct Point<Frame, Repr>(Repr, PhantomData<std::sync::Mutex<Frame>>);
+
+/// `Displacement<Frame, [Repr; N]>` is a vector between two
+/// [`Point`](struct.Point.html)s of the `Frame` reference frame.
+///
+/// Displacements support vector arithmetics:
+///
+/// ```
+/// use phantom_newtype::Displacement;
+///
+/// enum World {}
+/// type Vector = Displacement<World, [f64; 2]>;
+///
+/// let v = Vector::new([3.0, 4.0]);
+/// let w = Vector::new([1.0, -1.0]);
+///
+/// assert_eq!(v + w, Vector::new([4.0, 3.0]));
+/// assert_eq!(v - w, Vector::new([2.0, 5.0]));
+/// assert_eq!(-w, Vector::new([-1.0, 1.0]));
+/// assert_eq!(v * 2.0, Vector::new([6.0, 8.0]));
+/// assert_eq!(v / 2.0, Vector::new([1.5, 2.0]));
+/// assert_eq!(v.dot(w), -1.0);
+/// assert_eq!(v.norm_squared(), 25.0);
+/// assert_eq!(v.norm(), 5.0);
+/// assert_eq!(v.manhattan_norm(), 7.0);
+/// assert_eq!(Vector::default(), Vector::new([0.0, 0.0]));
+/// ```
+///
+/// Displacements are serialized as their coordinates.
+pub struct Displacement<Frame, Repr>(Repr, PhantomData<std::sync::Mutex<Frame>>);
+
+impl<Frame, Repr> Point<Frame, Repr> {
+    /// Constructs a point from its coordinates. This function can be
+    /// evaluated in compile time.
+    pub const fn new(coords: Repr) -> Point<Frame, Repr> {
+        Point(coords, PhantomData)
+    }
+}
+
+impl<Frame, Repr: Copy> Point<Frame, Repr> {
+    /// Returns the coordinates of the point.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Frame, Repr> Displacement<Frame, Repr> {
+    /// Constructs a displacement from its coordinates. This function
+    /// can be evaluated in compile time.
+    pub const fn new(coords: Repr) -> Displacement<Frame, Repr> {
+        Displacement(coords, PhantomData)
+    }
+}
+
+impl<Frame, Repr: Copy> Displacement<Frame, Repr> {
+    /// Returns the coordinates of the displacement.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Frame, T, const N: usize> Displacement<Frame, [T; N]>
+where
+    T: Add<Output = T> + Mul<Output = T> + Default + Copy,
+{
+    /// Returns the dot product of two displacements.
+    pub fn dot(&self, rhs: Self) -> T {
+        self.0
+            .iter()
+            .zip(rhs.0.iter())
+            .fold(T::default(), |acc, (a, b)| acc + *a * *b)
+    }
+
+    /// Returns the square of the Euclidean length of the displacement.
+    pub fn norm_squared(&self) -> T {
+        self.dot(*self)
+    }
+}
+
+macro_rules! impl_float_norms {
+    ($($repr:ident),*) => {
+        $(
+            impl<Frame, const N: usize> Displacement<Frame, [$repr; N]> {
+                /// Returns the Euclidean length of the displacement.
+                pub fn norm(&self) -> $repr {
+                    self.norm_squared().sqrt()
+                }
+
+                /// Returns the sum of absolute values of the
+                /// coordinates.
+                pub fn manhattan_norm(&self) -> $repr {
+                    self.0.iter().map(|x| x.abs()).sum()
+                }
+            }
+
+            impl<Frame, const N: usize> Point<Frame, [$repr; N]> {
+                /// Returns the Euclidean distance between two points.
+                pub fn distance(&self, other: Self) -> $repr {
+                    (*self - other).norm()
+                }
+            }
+        )*
+    };
+}
+
+impl_float_norms!(f32, f64);
+
+fn zip_with<T: Copy, const N: usize>(a: [T; N], b: [T; N], f: impl Fn(T, T) -> T) -> [T; N] {
+    let mut result = a;
+    for (x, y) in result.iter_mut().zip(b.iter()) {
+        *x = f(*x, *y);
+    }
+    result
+}
+
+impl<Frame, T: Copy, const N: usize> From<[T; N]> for Point<Frame, [T; N]> {
+    fn from(coords: [T; N]) -> Self {
+        Self::new(coords)
+    }
+}
+
+impl<Frame, T: Copy, const N: usize> From<[T; N]> for Displacement<Frame, [T; N]> {
+    fn from(coords: [T; N]) -> Self {
+        Self::new(coords)
+    }
+}
+
+impl<Frame, Repr: Copy> Clone for Point<Frame, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Frame, Repr: Copy> Copy for Point<Frame, Repr> {}
+
+impl<Frame, Repr: PartialEq> PartialEq for Point<Frame, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Frame, Repr: Eq> Eq for Point<Frame, Repr> {}
+
+impl<Frame, Repr: Hash> Hash for Point<Frame, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Frame, Repr: Copy> Clone for Displacement<Frame, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Frame, Repr: Copy> Copy for Displacement<Frame, Repr> {}
+
+impl<Frame, Repr: PartialEq> PartialEq for Displacement<Frame, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Frame, Repr: Eq> Eq for Displacement<Frame, Repr> {}
+
+impl<Frame, Repr: Hash> Hash for Displacement<Frame, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Frame, T: Default + Copy, const N: usize> Default for Displacement<Frame, [T; N]> {
+    fn default() -> Self {
+        Self::new([T::default(); N])
+    }
+}
+
+impl<Frame, T, const N: usize> Sub for Point<Frame, [T; N]>
+where
+    T: Sub<Output = T> + Copy,
+{
+    type Output = Displacement<Frame, [T; N]>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Displacement::new(zip_with(self.0, rhs.0, |a, b| a - b))
+    }
+}
+
+impl<Frame, T, const N: usize> Add<Displacement<Frame, [T; N]>> for Point<Frame, [T; N]>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Displacement<Frame, [T; N]>) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<Frame, T, const N: usize> AddAssign<Displacement<Frame, [T; N]>> for Point<Frame, [T; N]>
+where
+    T: Add<Output = T> + Copy,
+{
+    fn add_assign(&mut self, rhs: Displacement<Frame, [T; N]>) {
+        self.0 = zip_with(self.0, rhs.0, |a, b| a + b)
+    }
+}
+
+impl<Frame, T, const N: usize> Sub<Displacement<Frame, [T; N]>> for Point<Frame, [T; N]>
+where
+    T: Sub<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Displacement<Frame, [T; N]>) -> Self {
+        self.sub_assign(rhs);
+        self
+    }
+}
+
+impl<Frame, T, const N: usize> SubAssign<Displacement<Frame, [T; N]>> for Point<Frame, [T; N]>
+where
+    T: Sub<Output = T> + Copy,
+{
+    fn sub_assign(&mut self, rhs: Displacement<Frame, [T; N]>) {
+        self.0 = zip_with(self.0, rhs.0, |a, b| a - b)
+    }
+}
+
+impl<Frame, T, const N: usize> Add for Displacement<Frame, [T; N]>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<Frame, T, const N: usize> AddAssign for Displacement<Frame, [T; N]>
+where
+    T: Add<Output = T> + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = zip_with(self.0, rhs.0, |a, b| a + b)
+    }
+}
+
+impl<Frame, T, const N: usize> Sub for Displacement<Frame, [T; N]>
+where
+    T: Sub<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self.sub_assign(rhs);
+        self
+    }
+}
+
+impl<Frame, T, const N: usize> SubAssign for Displacement<Frame, [T; N]>
+where
+    T: Sub<Output = T> + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = zip_with(self.0, rhs.0, |a, b| a - b)
+    }
+}
+
+impl<Frame, T, const N: usize> Neg for Displacement<Frame, [T; N]>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(self.0.map(|x| -x))
+    }
+}
+
+impl<Frame, T, const N: usize> Mul<T> for Displacement<Frame, [T; N]>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.0.map(|x| x * rhs))
+    }
+}
+
+impl<Frame, T, const N: usize> Div<T> for Displacement<Frame, [T; N]>
+where
+    T: Div<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Self::new(self.0.map(|x| x / rhs))
+    }
+}
+
+impl<Frame, Repr> fmt::Debug for Point<Frame, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Frame, Repr> fmt::Debug for Displacement<Frame, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Frame, Repr: Serialize> Serialize for Point<Frame, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Frame, Repr> Deserialize<'de> for Point<Frame, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Point::<Frame, Repr>::new)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Frame, Repr: Serialize> Serialize for Displacement<Frame, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Frame, Repr> Deserialize<'de> for Displacement<Frame, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Displacement::<Frame, Repr>::new)
+    }
+}
@@ -0,0 +1,544 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Overflow policies selected by the unit of an amount or an instant.
+//!
+//! The policies drive the operators of the dedicated `PolicyAmount` and
+//! `PolicyInstant` archetypes rather than those of `Amount` and
+//! `Instant`. The operators of `Amount<Unit, Repr>` are implemented for
+//! every `Unit`, and without specialization a second set of impls for
+//! units implementing `ArithmeticPolicy` would overlap with them.
+//! Requiring all units to declare a policy instead would break every
+//! existing marker type.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use crate::integer::IntegerRepr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Describes how to handle overflows in arithmetics on `T`.
+pub trait OverflowPolicy<T> {
+    /// The result of an operation.
+    type Output;
+
+    /// Computes the result of an operation given its checked,
+    /// saturating and wrapping flavors. Only the flavor the policy
+    /// returns is evaluated.
+    fn resolve(
+        checked: impl FnOnce() -> Option<T>,
+        saturated: impl FnOnce() -> T,
+        wrapped: impl FnOnce() -> T,
+    ) -> Self::Output;
+}
+
+/// Panics on overflow, in all build profiles.
+pub enum PanicOnOverflow {}
+
+/// Saturates at the bounds of the representation.
+pub enum Saturate {}
+
+/// Wraps around at the bounds of the representation.
+pub enum Wrap {}
+
+/// Returns `None` on overflow.
+pub enum Checked {}
+
+impl<T> OverflowPolicy<T> for PanicOnOverflow {
+    type Output = T;
+
+    fn resolve(
+        checked: impl FnOnce() -> Option<T>,
+        _saturated: impl FnOnce() -> T,
+        _wrapped: impl FnOnce() -> T,
+    ) -> T {
+        checked().expect("arithmetic operation overflowed")
+    }
+}
+
+impl<T> OverflowPolicy<T> for Saturate {
+    type Output = T;
+
+    fn resolve(
+        _checked: impl FnOnce() -> Option<T>,
+        saturated: impl FnOnce() -> T,
+        _wrapped: impl FnOnce() -> T,
+    ) -> T {
+        saturated()
+    }
+}
+
+impl<T> OverflowPolicy<T> for Wrap {
+    type Output = T;
+
+    fn resolve(
+        _checked: impl FnOnce() -> Option<T>,
+        _saturated: impl FnOnce() -> T,
+        wrapped: impl FnOnce() -> T,
+    ) -> T {
+        wrapped()
+    }
+}
+
+impl<T> OverflowPolicy<T> for Checked {
+    type Output = Option<T>;
+
+    fn resolve(
+        checked: impl FnOnce() -> Option<T>,
+        _saturated: impl FnOnce() -> T,
+        _wrapped: impl FnOnce() -> T,
+    ) -> Option<T> {
+        checked()
+    }
+}
+
+/// A trait for markers of [`PolicyAmount`](struct.PolicyAmount.html)s
+/// and [`PolicyInstant`](struct.PolicyInstant.html)s that selects how
+/// their operators handle overflows.
+pub trait ArithmeticPolicy {
+    /// One of [`PanicOnOverflow`](enum.PanicOnOverflow.html),
+    /// [`Saturate`](enum.Saturate.html), [`Wrap`](enum.Wrap.html) or
+    /// [`Checked`](enum.Checked.html).
+    type Policy;
+}
+
+type PolicyOutput<Unit, T> = <<Unit as ArithmeticPolicy>::Policy as OverflowPolicy<T>>::Output;
+
+/// `PolicyAmount<Unit, Repr>` is an amount of some `Unit` whose
+/// operators (`+`, `-`, `*`) handle overflows according to the
+/// [`ArithmeticPolicy`](trait.ArithmeticPolicy.html) of the `Unit`.
+/// Unlike the operators of [`Amount`](struct.Amount.html), they behave
+/// the same way in all build profiles.
+///
+/// The policy can't change the operators of `Amount` itself: they are
+/// implemented for all units, and impls restricted to units with a
+/// policy would overlap with them. Convert with `from_amount` and
+/// `to_amount` where the policy should apply.
+///
+/// ```
+/// use phantom_newtype::{ArithmeticPolicy, Checked, PanicOnOverflow, PolicyAmount, Saturate};
+///
+/// enum Cycles {}
+/// impl ArithmeticPolicy for Cycles {
+///     type Policy = Saturate;
+/// }
+///
+/// enum Cents {}
+/// impl ArithmeticPolicy for Cents {
+///     type Policy = PanicOnOverflow;
+/// }
+///
+/// enum Ticks {}
+/// impl ArithmeticPolicy for Ticks {
+///     type Policy = Checked;
+/// }
+///
+/// type CycleCount = PolicyAmount<Cycles, u64>;
+///
+/// let mut cycles = CycleCount::from(u64::MAX - 1);
+/// cycles += CycleCount::from(5);
+/// assert_eq!(cycles, CycleCount::from(u64::MAX));
+/// assert_eq!(CycleCount::from(1) - CycleCount::from(5), CycleCount::from(0));
+///
+/// let ticks = PolicyAmount::<Ticks, u32>::from(10);
+/// assert_eq!(ticks * 2, Some(PolicyAmount::from(20)));
+/// assert_eq!(ticks - PolicyAmount::from(11), None);
+///
+/// let result = std::panic::catch_unwind(|| {
+///     PolicyAmount::<Cents, i64>::from(i64::MAX) * 2
+/// });
+/// assert!(result.is_err());
+/// ```
+///
+/// Amounts with the `Checked` policy return an `Option`, so they don't
+/// support compound assignment:
+///
+/// ```compile_fail
+/// use phantom_newtype::{ArithmeticPolicy, Checked, PolicyAmount};
+///
+/// enum Ticks {}
+/// impl ArithmeticPolicy for Ticks {
+///     type Policy = Checked;
+/// }
+///
+/// let mut ticks = PolicyAmount::<Ticks, u32>::from(10);
+/// ticks += PolicyAmount::from(1);
+/// ```
+///
+/// Units without a policy don't get the operators:
+///
+/// ```compile_fail
+/// use phantom_newtype::PolicyAmount;
+///
+/// enum Apples {}
+///
+/// let apples = PolicyAmount::<Apples, u64>::from(1) + PolicyAmount::from(1);
+/// ```
+///
+/// Policy amounts are converted from and to regular amounts:
+///
+/// ```
+/// use phantom_newtype::{Amount, ArithmeticPolicy, PolicyAmount, Wrap};
+///
+/// enum Counter {}
+/// impl ArithmeticPolicy for Counter {
+///     type Policy = Wrap;
+/// }
+///
+/// let count = PolicyAmount::from_amount(Amount::<Counter, u8>::from(255));
+/// assert_eq!((count + PolicyAmount::from(2)).to_amount(), Amount::from(1));
+/// ```
+pub struct PolicyAmount<Unit, Repr>(Repr, PhantomData<std::sync::Mutex<Unit>>);
+
+/// `PolicyInstant<Unit, Repr>` is an instant measured in some `Unit`
+/// whose operators handle overflows according to the
+/// [`ArithmeticPolicy`](trait.ArithmeticPolicy.html) of the `Unit`,
+/// see [`PolicyAmount`](struct.PolicyAmount.html).
+///
+/// ```
+/// use phantom_newtype::{ArithmeticPolicy, Checked, PolicyAmount, PolicyInstant};
+///
+/// enum Ticks {}
+/// impl ArithmeticPolicy for Ticks {
+///     type Policy = Checked;
+/// }
+///
+/// let tick = PolicyInstant::<Ticks, u32>::from(10);
+/// assert_eq!(tick + PolicyAmount::from(5), Some(PolicyInstant::from(15)));
+/// assert_eq!(tick - PolicyAmount::from(11), None);
+/// assert_eq!(tick - PolicyInstant::from(4), Some(PolicyAmount::from(6)));
+/// assert_eq!(tick - PolicyInstant::from(11), None);
+/// ```
+pub struct PolicyInstant<Unit, Repr>(Repr, PhantomData<std::sync::Mutex<Unit>>);
+
+macro_rules! impl_policy_archetype {
+    ($($archetype:ident($plain:ident, $from:ident, $to:ident)),*) => {
+        $(
+            impl<Unit, Repr> $archetype<Unit, Repr> {
+                /// `new` can be evaluated in compile time. The main
+                /// use-case of this functions is defining constants.
+                pub const fn new(repr: Repr) -> $archetype<Unit, Repr> {
+                    $archetype(repr, PhantomData)
+                }
+            }
+
+            impl<Unit, Repr: Copy> $archetype<Unit, Repr> {
+                /// Returns the wrapped value.
+                pub fn get(&self) -> Repr {
+                    self.0
+                }
+
+                #[doc = concat!("Converts a regular `", stringify!($plain), "`.")]
+                pub fn $from(value: $plain<Unit, Repr>) -> Self {
+                    Self::new(value.get())
+                }
+
+                #[doc = concat!("Converts the value into a regular `", stringify!($plain), "`.")]
+                pub fn $to(&self) -> $plain<Unit, Repr> {
+                    $plain::new(self.0)
+                }
+            }
+
+            impl<Unit, Repr: Copy> From<Repr> for $archetype<Unit, Repr> {
+                fn from(repr: Repr) -> Self {
+                    Self::new(repr)
+                }
+            }
+
+            impl<Unit, Repr: Copy> Clone for $archetype<Unit, Repr> {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl<Unit, Repr: Copy> Copy for $archetype<Unit, Repr> {}
+
+            impl<Unit, Repr: PartialEq> PartialEq for $archetype<Unit, Repr> {
+                fn eq(&self, rhs: &Self) -> bool {
+                    self.0.eq(&rhs.0)
+                }
+            }
+
+            impl<Unit, Repr: Eq> Eq for $archetype<Unit, Repr> {}
+
+            impl<Unit, Repr: PartialOrd> PartialOrd for $archetype<Unit, Repr> {
+                fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                    self.0.partial_cmp(&rhs.0)
+                }
+            }
+
+            impl<Unit, Repr: Ord> Ord for $archetype<Unit, Repr> {
+                fn cmp(&self, rhs: &Self) -> Ordering {
+                    self.0.cmp(&rhs.0)
+                }
+            }
+
+            impl<Unit, Repr: Hash> Hash for $archetype<Unit, Repr> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.hash(state)
+                }
+            }
+
+            impl<Unit, Repr> Mul<Repr> for $archetype<Unit, Repr>
+            where
+                Unit: ArithmeticPolicy,
+                Unit::Policy: OverflowPolicy<Self>,
+                Repr: IntegerRepr,
+            {
+                type Output = PolicyOutput<Unit, Self>;
+
+                fn mul(self, rhs: Repr) -> Self::Output {
+                    let a = self.0;
+                    Unit::Policy::resolve(
+                        || a.checked_mul(rhs).map(Self::new),
+                        || Self::new(a.saturating_mul(rhs)),
+                        || Self::new(a.wrapping_mul(rhs)),
+                    )
+                }
+            }
+
+            impl<Unit, Repr> MulAssign<Repr> for $archetype<Unit, Repr>
+            where
+                Unit: ArithmeticPolicy,
+                Unit::Policy: OverflowPolicy<Self, Output = Self>,
+                Repr: IntegerRepr,
+            {
+                fn mul_assign(&mut self, rhs: Repr) {
+                    *self = *self * rhs
+                }
+            }
+
+            impl<Unit, Repr> fmt::Debug for $archetype<Unit, Repr>
+            where
+                Repr: fmt::Debug,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+
+            impl<Unit, Repr> fmt::Display for $archetype<Unit, Repr>
+            where
+                Repr: fmt::Display,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<Unit, Repr: Serialize> Serialize for $archetype<Unit, Repr> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de, Unit, Repr> Deserialize<'de> for $archetype<Unit, Repr>
+            where
+                Repr: Deserialize<'de>,
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Repr::deserialize(deserializer).map($archetype::<Unit, Repr>::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_policy_archetype!(
+    PolicyAmount(Amount, from_amount, to_amount),
+    PolicyInstant(Instant, from_instant, to_instant)
+);
+
+impl<Unit, Repr> Add for PolicyAmount<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self>,
+    Repr: IntegerRepr,
+{
+    type Output = PolicyOutput<Unit, Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        Unit::Policy::resolve(
+            || a.checked_add(b).map(Self::new),
+            || Self::new(a.saturating_add(b)),
+            || Self::new(a.wrapping_add(b)),
+        )
+    }
+}
+
+impl<Unit, Repr> AddAssign for PolicyAmount<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self, Output = Self>,
+    Repr: IntegerRepr,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<Unit, Repr> Sub for PolicyAmount<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self>,
+    Repr: IntegerRepr,
+{
+    type Output = PolicyOutput<Unit, Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        Unit::Policy::resolve(
+            || a.checked_sub(b).map(Self::new),
+            || Self::new(a.saturating_sub(b)),
+            || Self::new(a.wrapping_sub(b)),
+        )
+    }
+}
+
+impl<Unit, Repr> SubAssign for PolicyAmount<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self, Output = Self>,
+    Repr: IntegerRepr,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs
+    }
+}
+
+impl<Unit, Repr> Add<PolicyAmount<Unit, Repr>> for PolicyInstant<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self>,
+    Repr: IntegerRepr,
+{
+    type Output = PolicyOutput<Unit, Self>;
+
+    fn add(self, rhs: PolicyAmount<Unit, Repr>) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        Unit::Policy::resolve(
+            || a.checked_add(b).map(Self::new),
+            || Self::new(a.saturating_add(b)),
+            || Self::new(a.wrapping_add(b)),
+        )
+    }
+}
+
+impl<Unit, Repr> AddAssign<PolicyAmount<Unit, Repr>> for PolicyInstant<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self, Output = Self>,
+    Repr: IntegerRepr,
+{
+    fn add_assign(&mut self, rhs: PolicyAmount<Unit, Repr>) {
+        *self = *self + rhs
+    }
+}
+
+impl<Unit, Repr> Sub<PolicyAmount<Unit, Repr>> for PolicyInstant<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self>,
+    Repr: IntegerRepr,
+{
+    type Output = PolicyOutput<Unit, Self>;
+
+    fn sub(self, rhs: PolicyAmount<Unit, Repr>) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        Unit::Policy::resolve(
+            || a.checked_sub(b).map(Self::new),
+            || Self::new(a.saturating_sub(b)),
+            || Self::new(a.wrapping_sub(b)),
+        )
+    }
+}
+
+impl<Unit, Repr> SubAssign<PolicyAmount<Unit, Repr>> for PolicyInstant<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<Self, Output = Self>,
+    Repr: IntegerRepr,
+{
+    fn sub_assign(&mut self, rhs: PolicyAmount<Unit, Repr>) {
+        *self = *self - rhs
+    }
+}
+
+impl<Unit, Repr> Sub for PolicyInstant<Unit, Repr>
+where
+    Unit: ArithmeticPolicy,
+    Unit::Policy: OverflowPolicy<PolicyAmount<Unit, Repr>>,
+    Repr: IntegerRepr,
+{
+    type Output = PolicyOutput<Unit, PolicyAmount<Unit, Repr>>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        Unit::Policy::resolve(
+            || a.checked_sub(b).map(PolicyAmount::new),
+            || PolicyAmount::new(a.saturating_sub(b)),
+            || PolicyAmount::new(a.wrapping_sub(b)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Counter {}
+
+    impl ArithmeticPolicy for Counter {
+        type Policy = Wrap;
+    }
+
+    type Count = PolicyAmount<Counter, u8>;
+
+    #[test]
+    fn test_wrap_policy() {
+        let max = Count::from(255);
+
+        assert_eq!(max + Count::from(2), Count::from(1));
+        assert_eq!(Count::from(0) - Count::from(1), max);
+        assert_eq!(max * 2, Count::from(254));
+        assert_eq!(
+            PolicyInstant::<Counter, u8>::from(0) - PolicyInstant::from(1),
+            max
+        );
+
+        let mut tick = PolicyInstant::<Counter, u8>::from(250);
+        tick += Count::from(10);
+        assert_eq!(tick, PolicyInstant::from(4));
+        tick -= Count::from(5);
+        assert_eq!(tick, PolicyInstant::from(255));
+    }
+
+    #[test]
+    fn test_policies_evaluate_one_flavor() {
+        let unused = || -> u8 { unreachable!("flavor must not be evaluated") };
+
+        assert_eq!(Saturate::resolve(|| unreachable!(), || 1u8, unused), 1);
+        assert_eq!(Wrap::resolve(|| unreachable!(), unused, || 2u8), 2);
+        assert_eq!(Checked::resolve(|| Some(3u8), unused, unused), Some(3));
+        assert_eq!(PanicOnOverflow::resolve(|| Some(4u8), unused, unused), 4);
+    }
+}
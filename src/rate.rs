@@ -0,0 +1,361 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::displayer::{DisplayProxy, DisplayerOf};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+/// `Rate<Num, Den, Repr>` provides a type-safe way to keep an amount
+/// of `Num` units per one `Den` unit (bytes per second, cents per
+/// kilowatt-hour, etc).
+///
+/// Unlike [`Per`](struct.Per.html) from the unit algebra, rates work
+/// with arbitrary markers and don't take part in further products and
+/// quotients.
+///
+/// Rates are usually obtained by dividing amounts of different units
+/// using [`Amount::per`](struct.Amount.html#method.per):
+///
+/// ```
+/// use phantom_newtype::{Amount, Rate};
+///
+/// enum Bytes {}
+/// enum Seconds {}
+///
+/// let transferred = Amount::<Bytes, u64>::from(1000);
+/// let elapsed = Amount::<Seconds, u64>::from(10);
+///
+/// let throughput: Rate<Bytes, Seconds, u64> = transferred.per(elapsed);
+/// assert_eq!(throughput, Rate::from(100));
+/// ```
+///
+/// Multiplying a rate by an amount of `Den` units gives an amount of
+/// `Num` units, dividing an amount of `Num` units by a rate gives an
+/// amount of `Den` units:
+///
+/// ```
+/// use phantom_newtype::{Amount, Rate};
+///
+/// enum Bytes {}
+/// enum Seconds {}
+///
+/// let throughput = Rate::<Bytes, Seconds, u64>::from(100);
+///
+/// assert_eq!(throughput * Amount::<Seconds, u64>::from(5),
+///            Amount::<Bytes, u64>::from(500));
+/// assert_eq!(Amount::<Seconds, u64>::from(5) * throughput,
+///            Amount::<Bytes, u64>::from(500));
+/// assert_eq!(Amount::<Bytes, u64>::from(500) / throughput,
+///            Amount::<Seconds, u64>::from(5));
+/// ```
+///
+/// Units of the rate must match units of the amounts, e.g. the
+/// following code must not compile:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Amount, Rate};
+///
+/// enum Bytes {}
+/// enum Seconds {}
+///
+/// let throughput = Rate::<Bytes, Seconds, u64>::from(100);
+/// let _ = throughput * Amount::<Bytes, u64>::from(5);
+/// ```
+///
+/// Rates with the same units can be compared, added and subtracted:
+///
+/// ```
+/// use phantom_newtype::Rate;
+///
+/// enum Bytes {}
+/// enum Seconds {}
+/// type Throughput = Rate<Bytes, Seconds, u64>;
+///
+/// assert!(Throughput::from(3) < Throughput::from(5));
+/// assert_eq!(Throughput::from(3) + Throughput::from(5), Throughput::from(8));
+/// assert_eq!(Throughput::from(5) - Throughput::from(3), Throughput::from(2));
+/// ```
+///
+/// Rates can be serialized and deserialized with `serde`. Serialized
+/// forms of `Rate<Num, Den, Repr>` and `Repr` are identical.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Rate;
+/// use serde::{Serialize, Deserialize};
+/// use serde_json;
+///
+/// enum Bytes {}
+/// enum Seconds {}
+/// type Throughput = Rate<Bytes, Seconds, u64>;
+///
+/// let repr: u64 = 10;
+/// let rate = Throughput::from(repr);
+/// assert_eq!(serde_json::to_string(&rate).unwrap(), serde_json::to_string(&repr).unwrap());
+///
+/// let copy: Throughput = serde_json::from_str(&serde_json::to_string(&rate).unwrap()).unwrap();
+/// assert_eq!(copy, rate);
+/// }
+/// ```
+///
+/// Rates can be sent between threads if the `Repr` allows it, no
+/// matter which units are used.
+///
+/// ```
+/// use phantom_newtype::Rate;
+///
+/// type Cell = std::cell::RefCell<i64>;
+/// type CellsPerCell = Rate<Cell, Cell, i64>;
+/// const R: CellsPerCell = CellsPerCell::new(1);
+///
+/// let r_from_thread = std::thread::spawn(|| &R).join().unwrap();
+/// assert_eq!(R, *r_from_thread);
+/// ```
+pub struct Rate<Num, Den, Repr>(Repr, PhantomData<std::sync::Mutex<(Num, Den)>>);
+
+impl<Num, Den, Repr: Copy> Rate<Num, Den, Repr> {
+    /// Returns the wrapped value.
+    ///
+    /// ```
+    /// use phantom_newtype::Rate;
+    ///
+    /// enum Bytes {}
+    /// enum Seconds {}
+    ///
+    /// let throughput = Rate::<Bytes, Seconds, u64>::from(100);
+    /// assert_eq!(100, throughput.get());
+    /// ```
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Num, Den, Repr> Rate<Num, Den, Repr> {
+    /// `new` is a synonym for `from` that can be evaluated in
+    /// compile time. The main use-case of this functions is defining
+    /// constants.
+    pub const fn new(repr: Repr) -> Rate<Num, Den, Repr> {
+        Rate(repr, PhantomData)
+    }
+}
+
+impl<Num, Den, Repr> Rate<Num, Den, Repr>
+where
+    Num: DisplayerOf<Rate<Num, Den, Repr>>,
+{
+    /// `display` provides a machanism to implement a custom display
+    /// for phantom types.
+    ///
+    /// ```
+    /// use phantom_newtype::{Rate, DisplayerOf};
+    /// use std::fmt;
+    ///
+    /// enum Bytes {}
+    /// enum Seconds {}
+    /// type Throughput = Rate<Bytes, Seconds, u64>;
+    ///
+    /// impl DisplayerOf<Throughput> for Bytes {
+    ///   fn display(rate: &Throughput, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///     write!(f, "{} B/s", rate.get())
+    ///   }
+    /// }
+    ///
+    /// assert_eq!(format!("{}", Throughput::from(100).display()), "100 B/s");
+    /// ```
+    pub fn display(&self) -> DisplayProxy<'_, Self, Num> {
+        DisplayProxy::new(self)
+    }
+}
+
+impl<Unit, Repr> Amount<Unit, Repr>
+where
+    Repr: Div + Copy,
+{
+    /// Divides this amount by an amount of different units, producing
+    /// a [`Rate`](struct.Rate.html).
+    ///
+    /// Note that the `/` operator can't be used for this purpose:
+    /// dividing amounts of the same unit already produces a scalar,
+    /// and Rust doesn't allow us to distinguish these cases.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Rate};
+    ///
+    /// enum Meters {}
+    /// enum Seconds {}
+    ///
+    /// let distance = Amount::<Meters, u64>::from(100);
+    /// let time = Amount::<Seconds, u64>::from(20);
+    ///
+    /// assert_eq!(distance.per(time), Rate::<Meters, Seconds, u64>::from(5));
+    /// ```
+    pub fn per<Den>(self, rhs: Amount<Den, Repr>) -> Rate<Unit, Den, <Repr as Div>::Output> {
+        Rate::new(self.get() / rhs.get())
+    }
+}
+
+impl<Num, Den, Repr: Copy> From<Repr> for Rate<Num, Den, Repr> {
+    fn from(repr: Repr) -> Self {
+        Self::new(repr)
+    }
+}
+
+impl<Num, Den, Repr: Copy> Clone for Rate<Num, Den, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Num, Den, Repr: Copy> Copy for Rate<Num, Den, Repr> {}
+
+impl<Num, Den, Repr: PartialEq> PartialEq for Rate<Num, Den, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Num, Den, Repr: Eq> Eq for Rate<Num, Den, Repr> {}
+
+impl<Num, Den, Repr: PartialOrd> PartialOrd for Rate<Num, Den, Repr> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Num, Den, Repr: Ord> Ord for Rate<Num, Den, Repr> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Num, Den, Repr: Hash> Hash for Rate<Num, Den, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Num, Den, Repr> Add for Rate<Num, Den, Repr>
+where
+    Repr: AddAssign + Copy,
+{
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<Num, Den, Repr> AddAssign for Rate<Num, Den, Repr>
+where
+    Repr: AddAssign + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.get()
+    }
+}
+
+impl<Num, Den, Repr> SubAssign for Rate<Num, Den, Repr>
+where
+    Repr: SubAssign + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.get()
+    }
+}
+
+impl<Num, Den, Repr> Sub for Rate<Num, Den, Repr>
+where
+    Repr: SubAssign + Copy,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self.sub_assign(rhs);
+        self
+    }
+}
+
+impl<Num, Den, Repr> Mul<Amount<Den, Repr>> for Rate<Num, Den, Repr>
+where
+    Repr: Mul + Copy,
+{
+    type Output = Amount<Num, <Repr as Mul>::Output>;
+
+    fn mul(self, rhs: Amount<Den, Repr>) -> Self::Output {
+        Amount::new(self.0 * rhs.get())
+    }
+}
+
+impl<Num, Den, Repr> Mul<Rate<Num, Den, Repr>> for Amount<Den, Repr>
+where
+    Repr: Mul + Copy,
+{
+    type Output = Amount<Num, <Repr as Mul>::Output>;
+
+    fn mul(self, rhs: Rate<Num, Den, Repr>) -> Self::Output {
+        Amount::new(self.get() * rhs.0)
+    }
+}
+
+impl<Num, Den, Repr> Div<Rate<Num, Den, Repr>> for Amount<Num, Repr>
+where
+    Repr: Div + Copy,
+{
+    type Output = Amount<Den, <Repr as Div>::Output>;
+
+    fn div(self, rhs: Rate<Num, Den, Repr>) -> Self::Output {
+        Amount::new(self.get() / rhs.0)
+    }
+}
+
+impl<Num, Den, Repr> fmt::Debug for Rate<Num, Den, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Num, Den, Repr> fmt::Display for Rate<Num, Den, Repr>
+where
+    Repr: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Num, Den, Repr: Serialize> Serialize for Rate<Num, Den, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Num, Den, Repr> Deserialize<'de> for Rate<Num, Den, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Rate::<Num, Den, Repr>::new)
+    }
+}
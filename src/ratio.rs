@@ -0,0 +1,221 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use crate::rounding::{div_round, from_i128, to_i128, Rounding};
+use std::convert::{TryFrom, TryInto};
+use std::marker::PhantomData;
+
+/// Declares the size of a unit relative to a `Base` unit: one `Self`
+/// is `NUMERATOR / DENOMINATOR` of `Base`. The base unit itself must
+/// implement the trait with the ratio `1 / 1`.
+///
+/// Amounts and instants of units with the same base can be converted
+/// into each other. Conversions to finer units are exact and only
+/// fail on overflow:
+///
+/// ```
+/// use phantom_newtype::{Amount, Instant, Rounding, UnitRatio};
+///
+/// enum Seconds {}
+/// enum Millis {}
+///
+/// impl UnitRatio for Seconds {
+///     type Base = Seconds;
+///     const NUMERATOR: u64 = 1;
+/// }
+///
+/// impl UnitRatio for Millis {
+///     type Base = Seconds;
+///     const NUMERATOR: u64 = 1;
+///     const DENOMINATOR: u64 = 1000;
+/// }
+///
+/// let timeout = Amount::<Seconds, u32>::from(5);
+/// assert_eq!(timeout.convert::<Millis>(), Some(Amount::from(5000)));
+/// assert_eq!(Amount::<Seconds, u8>::from(1).convert::<Millis>(), None);
+///
+/// let deadline = Instant::<Millis, u64>::from(1999);
+/// assert_eq!(deadline.convert_rounded::<Seconds>(Rounding::Floor), Instant::from(1));
+/// assert_eq!(deadline.convert_rounded::<Seconds>(Rounding::Ceil), Instant::from(2));
+/// ```
+///
+/// Conversions to coarser units require a rounding mode:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Amount, UnitRatio};
+///
+/// enum Bytes {}
+/// enum KiB {}
+///
+/// impl UnitRatio for Bytes {
+///     type Base = Bytes;
+///     const NUMERATOR: u64 = 1;
+/// }
+///
+/// impl UnitRatio for KiB {
+///     type Base = Bytes;
+///     const NUMERATOR: u64 = 1024;
+/// }
+///
+/// let _ = Amount::<Bytes, u64>::from(2048).convert::<KiB>();
+/// ```
+///
+/// Units with different bases can't be converted into each other:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Amount, UnitRatio};
+///
+/// enum Bytes {}
+/// enum Seconds {}
+///
+/// impl UnitRatio for Bytes {
+///     type Base = Bytes;
+///     const NUMERATOR: u64 = 1;
+/// }
+///
+/// impl UnitRatio for Seconds {
+///     type Base = Seconds;
+///     const NUMERATOR: u64 = 1;
+/// }
+///
+/// let _ = Amount::<Bytes, u64>::from(1).convert::<Seconds>();
+/// ```
+pub trait UnitRatio {
+    /// The unit this unit is measured in.
+    type Base;
+    /// The number of base units in `DENOMINATOR` units, e.g. 1024 for
+    /// kibibytes measured in bytes.
+    const NUMERATOR: u64;
+    /// The number of units making up `NUMERATOR` base units, e.g. 1000
+    /// for milliseconds measured in seconds.
+    const DENOMINATOR: u64 = 1;
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// The ratio of `Src` to `Dst` in lowest terms: one `Src` is
+// `NUMERATOR / DENOMINATOR` of `Dst`.
+struct Conversion<Src, Dst>(PhantomData<(Src, Dst)>);
+
+impl<Src: UnitRatio, Dst: UnitRatio> Conversion<Src, Dst> {
+    const N: u128 = Src::NUMERATOR as u128 * Dst::DENOMINATOR as u128;
+    const D: u128 = Src::DENOMINATOR as u128 * Dst::NUMERATOR as u128;
+    const NUMERATOR: i128 = (Self::N / gcd(Self::N, Self::D)) as i128;
+    const DENOMINATOR: i128 = (Self::D / gcd(Self::N, Self::D)) as i128;
+    const EXACT: () = assert!(
+        Self::DENOMINATOR == 1,
+        "conversion to a coarser unit requires a rounding mode"
+    );
+}
+
+macro_rules! impl_convert {
+    ($($archetype:ident),*) => {
+        $(
+            impl<Unit, Repr> $archetype<Unit, Repr>
+            where
+                Unit: UnitRatio,
+                Repr: TryFrom<i128> + TryInto<i128> + Copy,
+            {
+                /// Converts the value into a finer unit with the same base,
+                /// returns `None` on overflow. Fails to compile if `To` is
+                /// not an integral fraction of `Unit`.
+                pub fn convert<To>(self) -> Option<$archetype<To, Repr>>
+                where
+                    To: UnitRatio<Base = Unit::Base>,
+                {
+                    #[allow(clippy::let_unit_value)]
+                    let () = Conversion::<Unit, To>::EXACT;
+                    let value: i128 = self.get().try_into().ok()?;
+                    value
+                        .checked_mul(Conversion::<Unit, To>::NUMERATOR)
+                        .and_then(|v| Repr::try_from(v).ok())
+                        .map($archetype::new)
+                }
+
+                /// Converts the value into any unit with the same base
+                /// rounding the result according to `rounding`. Panics if
+                /// the result doesn't fit into `Repr`.
+                pub fn convert_rounded<To>(self, rounding: Rounding) -> $archetype<To, Repr>
+                where
+                    To: UnitRatio<Base = Unit::Base>,
+                {
+                    let product = to_i128(self.get())
+                        .checked_mul(Conversion::<Unit, To>::NUMERATOR)
+                        .expect("attempt to multiply with overflow");
+                    $archetype::new(from_i128(div_round(
+                        product,
+                        Conversion::<Unit, To>::DENOMINATOR,
+                        rounding,
+                    )))
+                }
+            }
+        )*
+    };
+}
+
+impl_convert!(Amount, Instant);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Bytes {}
+    enum KiB {}
+    enum Kilobytes {}
+
+    impl UnitRatio for Bytes {
+        type Base = Bytes;
+        const NUMERATOR: u64 = 1;
+    }
+
+    impl UnitRatio for KiB {
+        type Base = Bytes;
+        const NUMERATOR: u64 = 1024;
+    }
+
+    impl UnitRatio for Kilobytes {
+        type Base = Bytes;
+        const NUMERATOR: u64 = 1000;
+    }
+
+    #[test]
+    fn test_convert() {
+        let size = Amount::<KiB, i32>::from(-3);
+
+        assert_eq!(size.convert::<Bytes>(), Some(Amount::from(-3072)));
+        assert_eq!(size.convert::<KiB>(), Some(size));
+        assert_eq!(Amount::<KiB, i32>::MAX.convert::<Bytes>(), None);
+        assert_eq!(
+            size.convert_rounded::<Kilobytes>(Rounding::TowardZero),
+            Amount::from(-3)
+        );
+        assert_eq!(
+            size.convert_rounded::<Kilobytes>(Rounding::Floor),
+            Amount::from(-4)
+        );
+        assert_eq!(
+            Amount::<Kilobytes, u64>::from(128).convert_rounded::<KiB>(Rounding::HalfEven),
+            Amount::from(125)
+        );
+    }
+}
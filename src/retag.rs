@@ -0,0 +1,51 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A marker trait that allows converting
+/// [`Amount`](struct.Amount.html)s, [`Id`](struct.Id.html)s and
+/// [`Instant`](struct.Instant.html)s tagged with `Self` into ones
+/// tagged with `To` using their `retag` methods.
+///
+/// Every place where values change their tag is explicit and easy to
+/// find:
+///
+/// ```
+/// use phantom_newtype::{Id, Retag};
+///
+/// enum LegacyUser {}
+/// enum User {}
+///
+/// impl Retag<User> for LegacyUser {}
+///
+/// let legacy_id = Id::<LegacyUser, u64>::from(42);
+/// let id: Id<User, u64> = legacy_id.retag();
+///
+/// assert_eq!(id, Id::from(42));
+/// assert_eq!(id.forget_tag(), 42);
+/// ```
+///
+/// Retagging is only allowed in the declared direction:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Id, Retag};
+///
+/// enum LegacyUser {}
+/// enum User {}
+///
+/// impl Retag<User> for LegacyUser {}
+///
+/// let id = Id::<User, u64>::from(42);
+/// let legacy_id: Id<LegacyUser, u64> = id.retag();
+/// ```
+pub trait Retag<To> {}
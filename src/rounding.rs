@@ -0,0 +1,192 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::{TryFrom, TryInto};
+
+/// Describes how to round results of operations that can't be
+/// represented exactly, e.g. integer division.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero, like the `/` operator on integers does.
+    TowardZero,
+    /// Round to the nearest integer, ties are rounded away from zero.
+    HalfAwayFromZero,
+    /// Round to the nearest integer, ties are rounded to the even
+    /// integer. Also known as banker's rounding.
+    HalfEven,
+}
+
+/// Divides `n` by `d` rounding the result according to `mode`.
+///
+/// Panics if `d` is zero or the result overflows.
+pub(crate) fn div_round(n: i128, d: i128, mode: Rounding) -> i128 {
+    assert!(d != 0, "attempt to divide by zero");
+    let (q, r) = (n / d, n % d);
+    if r == 0 {
+        return q;
+    }
+    // `q` is rounded towards zero, move it to the floor of `n / d`.
+    // Neither the floor nor the ceiling of an inexact quotient can
+    // overflow.
+    let negative = (n < 0) != (d < 0);
+    let floor = if negative { q - 1 } else { q };
+    let (r, d) = (r.unsigned_abs(), d.unsigned_abs());
+    // The distance from the floor to the exact quotient in `1/d`ths.
+    let above = if negative { d - r } else { r };
+    if round_up(mode, above, d - above, floor & 1 == 1, negative) {
+        floor + 1
+    } else {
+        floor
+    }
+}
+
+/// Divides `n` by `d` rounding the result according to `mode`, the
+/// unsigned counterpart of `div_round`.
+///
+/// Panics if `d` is zero.
+pub(crate) fn div_round_unsigned(n: u128, d: u128, mode: Rounding) -> u128 {
+    assert!(d != 0, "attempt to divide by zero");
+    let (q, r) = (n / d, n % d);
+    if r != 0 && round_up(mode, r, d - r, q & 1 == 1, false) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+// Decides whether to round an inexact quotient up from its floor given
+// the distances from the floor and to the ceiling.
+fn round_up(mode: Rounding, above: u128, below: u128, floor_is_odd: bool, negative: bool) -> bool {
+    match mode {
+        Rounding::Floor => false,
+        Rounding::Ceil => true,
+        Rounding::TowardZero => negative,
+        Rounding::HalfAwayFromZero => above > below || (above == below && !negative),
+        Rounding::HalfEven => above > below || (above == below && floor_is_odd),
+    }
+}
+
+/// Converts an integer representation to `i128`, panics if the value
+/// doesn't fit.
+pub(crate) fn to_i128<Repr: TryInto<i128>>(repr: Repr) -> i128 {
+    repr.try_into().ok().expect("value doesn't fit into i128")
+}
+
+/// Splits an integer representation into its sign and magnitude,
+/// `true` standing for negative values. Unlike `to_i128`, supports all
+/// primitive integers, including `u128` values above `i128::MAX`.
+pub(crate) fn to_sign_magnitude<Repr>(repr: Repr) -> (bool, u128)
+where
+    Repr: TryInto<i128> + TryInto<u128> + Copy,
+{
+    match TryInto::<u128>::try_into(repr) {
+        Ok(magnitude) => (false, magnitude),
+        Err(_) => (true, to_i128(repr).unsigned_abs()),
+    }
+}
+
+/// Converts an `i128` to an integer representation, panics if the
+/// value doesn't fit.
+pub(crate) fn from_i128<Repr: TryFrom<i128>>(value: i128) -> Repr {
+    Repr::try_from(value)
+        .ok()
+        .expect("value doesn't fit into the representation")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_round() {
+        let cases = [
+            // n, d, floor, ceil, toward zero, half away, half even
+            (7, 2, 3, 4, 3, 4, 4),
+            (5, 2, 2, 3, 2, 3, 2),
+            (-5, 2, -3, -2, -2, -3, -2),
+            (-7, 2, -4, -3, -3, -4, -4),
+            (7, 3, 2, 3, 2, 2, 2),
+            (8, 3, 2, 3, 2, 3, 3),
+            (-8, 3, -3, -2, -2, -3, -3),
+            (6, -4, -2, -1, -1, -2, -2),
+            (9, 3, 3, 3, 3, 3, 3),
+            (-6, -4, 1, 2, 1, 2, 2),
+            (-5, -4, 1, 2, 1, 1, 1),
+            (
+                i128::MIN,
+                -2,
+                1 << 126,
+                1 << 126,
+                1 << 126,
+                1 << 126,
+                1 << 126,
+            ),
+            (
+                i128::MIN + 1,
+                2,
+                -(1 << 126),
+                -(1 << 126) + 1,
+                -(1 << 126) + 1,
+                -(1 << 126),
+                -(1 << 126),
+            ),
+        ];
+        for &(n, d, floor, ceil, toward_zero, half_away, half_even) in cases.iter() {
+            assert_eq!(div_round(n, d, Rounding::Floor), floor, "{} / {}", n, d);
+            assert_eq!(div_round(n, d, Rounding::Ceil), ceil, "{} / {}", n, d);
+            assert_eq!(
+                div_round(n, d, Rounding::TowardZero),
+                toward_zero,
+                "{} / {}",
+                n,
+                d
+            );
+            assert_eq!(
+                div_round(n, d, Rounding::HalfAwayFromZero),
+                half_away,
+                "{} / {}",
+                n,
+                d
+            );
+            assert_eq!(
+                div_round(n, d, Rounding::HalfEven),
+                half_even,
+                "{} / {}",
+                n,
+                d
+            );
+        }
+    }
+
+    #[test]
+    fn test_div_round_unsigned() {
+        assert_eq!(
+            div_round_unsigned(u128::MAX, 2, Rounding::Floor),
+            u128::MAX / 2
+        );
+        assert_eq!(div_round_unsigned(u128::MAX, 2, Rounding::Ceil), 1 << 127);
+        assert_eq!(
+            div_round_unsigned(u128::MAX, 2, Rounding::HalfEven),
+            1 << 127
+        );
+        assert_eq!(div_round_unsigned(5, 2, Rounding::HalfEven), 2);
+        assert_eq!(div_round_unsigned(5, 2, Rounding::HalfAwayFromZero), 3);
+        assert_eq!(div_round_unsigned(5, 2, Rounding::TowardZero), 2);
+        assert_eq!(div_round_unsigned(u128::MAX, 1, Rounding::Ceil), u128::MAX);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+// A supertrait of the representation traits that downstream crates
+// must not implement. The trait is public but can't be named outside
+// of this crate because the module is private.
+pub trait Sealed {}
+
+macro_rules! impl_sealed {
+    ($($repr:ty),*) => {
+        $(
+            impl Sealed for $repr {}
+        )*
+    };
+}
+
+impl_sealed!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl_sealed!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+impl<Unit, Repr> Sealed for Amount<Unit, Repr> {}
+
+impl<Unit, Repr> Sealed for Instant<Unit, Repr> {}
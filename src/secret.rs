@@ -0,0 +1,177 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// A marker trait that allows serializing secrets of the `Kind` it's
+/// implemented for. Secrets can always be deserialized.
+pub trait SerializableSecret {}
+
+/// `Secret<Kind, Repr>` provides a type-safe way to keep API tokens,
+/// passwords and other sensitive values. Secrets are available with
+/// the `secret` feature.
+///
+/// Unlike other archetypes, secrets never reveal their value when
+/// formatted:
+///
+/// ```
+/// use phantom_newtype::Secret;
+///
+/// enum ApiToken {}
+/// type Token = Secret<ApiToken, String>;
+///
+/// let token = Token::from("hunter2".to_string());
+///
+/// assert_eq!(format!("{:?}", token), "[REDACTED]");
+/// assert_eq!(format!("{}", token), "[REDACTED]");
+/// assert_eq!(token.expose_secret(), "hunter2");
+/// ```
+///
+/// Secrets can be compared for equality using
+/// [`subtle::ConstantTimeEq`](https://docs.rs/subtle), the comparison
+/// doesn't depend on the contents of values of the same length.
+/// Secrets are neither hashed nor ordered:
+///
+/// ```compile_fail
+/// use phantom_newtype::Secret;
+/// use std::collections::HashSet;
+///
+/// enum ApiToken {}
+/// type Token = Secret<ApiToken, String>;
+///
+/// let mut tokens = HashSet::new();
+/// tokens.insert(Token::from("hunter2".to_string()));
+/// ```
+///
+/// The memory occupied by the value is zeroized when the secret is
+/// dropped, so `Repr` must implement
+/// [`zeroize::Zeroize`](https://docs.rs/zeroize).
+///
+/// Secrets can be deserialized with `serde`, serialization requires
+/// the `Kind` to implement [`SerializableSecret`](trait.SerializableSecret.html):
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::{Secret, SerializableSecret};
+/// use serde_json;
+///
+/// enum SessionKey {}
+/// impl SerializableSecret for SessionKey {}
+/// type Key = Secret<SessionKey, String>;
+///
+/// let key: Key = serde_json::from_str(r#""s3cr3t""#).unwrap();
+/// assert_eq!(key.expose_secret(), "s3cr3t");
+/// assert_eq!(serde_json::to_string(&key).unwrap(), r#""s3cr3t""#);
+/// }
+/// ```
+pub struct Secret<Kind, Repr: Zeroize>(Repr, PhantomData<std::sync::Mutex<Kind>>);
+
+impl<Kind, Repr: Zeroize> Secret<Kind, Repr> {
+    /// `new` is a synonym for `from` that can be evaluated in
+    /// compile time.
+    pub const fn new(repr: Repr) -> Secret<Kind, Repr> {
+        Secret(repr, PhantomData)
+    }
+
+    /// Returns a reference to the secret value. Calls of this function
+    /// mark the places where the secret leaves its wrapper.
+    pub fn expose_secret(&self) -> &Repr {
+        &self.0
+    }
+}
+
+impl<Kind, Repr: Zeroize> From<Repr> for Secret<Kind, Repr> {
+    fn from(repr: Repr) -> Self {
+        Self::new(repr)
+    }
+}
+
+impl<Kind, Repr: Zeroize> Drop for Secret<Kind, Repr> {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+impl<Kind, Repr: Zeroize + Clone> Clone for Secret<Kind, Repr> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<Kind, Repr> PartialEq for Secret<Kind, Repr>
+where
+    Repr: Zeroize + AsRef<[u8]>,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.as_ref().ct_eq(rhs.0.as_ref()).into()
+    }
+}
+
+impl<Kind, Repr> Eq for Secret<Kind, Repr> where Repr: Zeroize + AsRef<[u8]> {}
+
+impl<Kind, Repr: Zeroize> fmt::Debug for Secret<Kind, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl<Kind, Repr: Zeroize> fmt::Display for Secret<Kind, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Kind, Repr> Serialize for Secret<Kind, Repr>
+where
+    Kind: SerializableSecret,
+    Repr: Zeroize + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Kind, Repr> Deserialize<'de> for Secret<Kind, Repr>
+where
+    Repr: Zeroize + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Secret::<Kind, Repr>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Password {}
+    type Pass = Secret<Password, Vec<u8>>;
+
+    #[test]
+    fn test_eq() {
+        let pass = |s: &[u8]| Pass::new(s.to_vec());
+
+        assert!(pass(b"") == pass(b""));
+        assert!(pass(b"hunter2") == pass(b"hunter2"));
+        assert!(pass(b"hunter2") != pass(b"hunter3"));
+        assert!(pass(b"hunter2") != pass(b"hunter"));
+    }
+}
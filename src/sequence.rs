@@ -0,0 +1,317 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::amount::Amount;
+use crate::sealed::Sealed;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign};
+
+/// Representations of [`Sequence`](struct.Sequence.html) numbers,
+/// implemented by unsigned integer types. This trait is sealed and
+/// can't be implemented outside of this crate.
+pub trait SerialRepr: Copy + Eq + Sealed {
+    /// The signed integer type of the same size.
+    type Signed: Copy;
+
+    /// One value of the type.
+    const ONE: Self;
+
+    /// Adds two values wrapping around at the bounds of the type.
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /// Subtracts two values wrapping around at the bounds of the type.
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /// Returns `to - self` interpreted as a signed number.
+    fn signed_distance(self, to: Self) -> Self::Signed;
+
+    /// Compares serial numbers according to RFC 1982.
+    fn serial_cmp(self, other: Self) -> Option<Ordering>;
+}
+
+macro_rules! impl_serial_repr {
+    ($($repr:ident($signed:ident)),*) => {
+        $(
+            impl SerialRepr for $repr {
+                type Signed = $signed;
+
+                const ONE: Self = 1;
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    $repr::wrapping_add(self, rhs)
+                }
+
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    $repr::wrapping_sub(self, rhs)
+                }
+
+                fn signed_distance(self, to: Self) -> $signed {
+                    $repr::wrapping_sub(to, self) as $signed
+                }
+
+                fn serial_cmp(self, other: Self) -> Option<Ordering> {
+                    match other.signed_distance(self) {
+                        // The numbers are exactly half of the space
+                        // apart, RFC 1982 leaves the result undefined.
+                        $signed::MIN => None,
+                        d => Some(d.cmp(&0)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_serial_repr!(u8(i8), u16(i16), u32(i32), u64(i64), usize(isize));
+
+/// `Sequence<Kind, Repr>` is a serial number, e.g. a packet or a
+/// replication log sequence number, that wraps around when it
+/// overflows. Sequence numbers are compared using [RFC
+/// 1982](https://tools.ietf.org/html/rfc1982) serial number
+/// arithmetic, so the number right after the wraparound is still newer
+/// than the one right before it:
+///
+/// ```
+/// use phantom_newtype::Sequence;
+///
+/// enum Packet {}
+/// type PacketSeq = Sequence<Packet, u8>;
+///
+/// let last = PacketSeq::from(255);
+/// let first = last.next();
+///
+/// assert_eq!(first, PacketSeq::from(0));
+/// assert!(first.is_after(last));
+/// assert!(last.is_before(first));
+/// ```
+///
+/// Serial number order isn't transitive, that's why sequences don't
+/// implement `PartialOrd`:
+///
+/// ```compile_fail
+/// use phantom_newtype::Sequence;
+///
+/// enum Packet {}
+/// type PacketSeq = Sequence<Packet, u8>;
+///
+/// assert!(PacketSeq::from(0) < PacketSeq::from(1));
+/// ```
+///
+/// The distance between sequence numbers is a signed amount:
+///
+/// ```
+/// use phantom_newtype::{Amount, Sequence};
+///
+/// enum Packet {}
+/// type PacketSeq = Sequence<Packet, u16>;
+///
+/// let a = PacketSeq::from(65530);
+/// let b = PacketSeq::from(4);
+///
+/// assert_eq!(a.distance_to(b), Amount::<Packet, i16>::from(10));
+/// assert_eq!(b.distance_to(a), Amount::<Packet, i16>::from(-10));
+/// assert_eq!(a + Amount::from(10), b);
+/// ```
+///
+/// Sequences are serialized as their `Repr`.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Sequence;
+/// use serde_json;
+///
+/// enum Packet {}
+/// type PacketSeq = Sequence<Packet, u32>;
+///
+/// let seq = PacketSeq::from(42);
+/// assert_eq!(serde_json::to_string(&seq).unwrap(), "42");
+/// assert_eq!(serde_json::from_str::<PacketSeq>("42").unwrap(), seq);
+/// }
+/// ```
+pub struct Sequence<Kind, Repr>(Repr, PhantomData<std::sync::Mutex<Kind>>);
+
+impl<Kind, Repr: Copy> Sequence<Kind, Repr> {
+    /// Returns the wrapped value.
+    pub fn get(&self) -> Repr {
+        self.0
+    }
+}
+
+impl<Kind, Repr> Sequence<Kind, Repr> {
+    /// `new` is a synonym for `from` that can be evaluated in
+    /// compile time. The main use-case of this functions is defining
+    /// constants.
+    pub const fn new(repr: Repr) -> Sequence<Kind, Repr> {
+        Sequence(repr, PhantomData)
+    }
+}
+
+impl<Kind, Repr: SerialRepr> Sequence<Kind, Repr> {
+    /// Returns the sequence number following this one.
+    pub fn next(&self) -> Self {
+        Self::new(self.0.wrapping_add(Repr::ONE))
+    }
+
+    /// Compares sequence numbers according to RFC 1982. Returns `None`
+    /// if the numbers are exactly half of the number space apart.
+    pub fn serial_cmp(&self, other: Self) -> Option<Ordering> {
+        self.0.serial_cmp(other.0)
+    }
+
+    /// Checks whether this sequence number is newer than `other`.
+    pub fn is_after(&self, other: Self) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Greater)
+    }
+
+    /// Checks whether this sequence number is older than `other`.
+    pub fn is_before(&self, other: Self) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Less)
+    }
+
+    /// Returns the number of steps from this sequence number to
+    /// `other`, negative if `other` is older.
+    pub fn distance_to(&self, other: Self) -> Amount<Kind, Repr::Signed> {
+        Amount::new(self.0.signed_distance(other.0))
+    }
+
+    /// Checks whether this sequence number belongs to the window of
+    /// `len` numbers starting with `start`, taking wraparound into
+    /// account.
+    ///
+    /// ```
+    /// use phantom_newtype::{Amount, Sequence};
+    ///
+    /// enum Packet {}
+    /// type PacketSeq = Sequence<Packet, u8>;
+    ///
+    /// let start = PacketSeq::from(250);
+    /// let window = Amount::from(10);
+    ///
+    /// assert!(PacketSeq::from(250).is_within(start, window));
+    /// assert!(PacketSeq::from(3).is_within(start, window));
+    /// assert!(!PacketSeq::from(4).is_within(start, window));
+    /// assert!(!PacketSeq::from(249).is_within(start, window));
+    /// ```
+    pub fn is_within(&self, start: Self, len: Amount<Kind, Repr>) -> bool
+    where
+        Repr: Ord,
+    {
+        self.0.wrapping_sub(start.0) < len.get()
+    }
+}
+
+impl<Kind, Repr: Copy> From<Repr> for Sequence<Kind, Repr> {
+    fn from(repr: Repr) -> Self {
+        Self::new(repr)
+    }
+}
+
+impl<Kind, Repr: Copy> Clone for Sequence<Kind, Repr> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Kind, Repr: Copy> Copy for Sequence<Kind, Repr> {}
+
+impl<Kind, Repr: PartialEq> PartialEq for Sequence<Kind, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Kind, Repr: Eq> Eq for Sequence<Kind, Repr> {}
+
+impl<Kind, Repr: Hash> Hash for Sequence<Kind, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Kind, Repr: SerialRepr> Add<Amount<Kind, Repr>> for Sequence<Kind, Repr> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Amount<Kind, Repr>) -> Self {
+        self.add_assign(rhs);
+        self
+    }
+}
+
+impl<Kind, Repr: SerialRepr> AddAssign<Amount<Kind, Repr>> for Sequence<Kind, Repr> {
+    fn add_assign(&mut self, rhs: Amount<Kind, Repr>) {
+        self.0 = self.0.wrapping_add(rhs.get())
+    }
+}
+
+impl<Kind, Repr> fmt::Debug for Sequence<Kind, Repr>
+where
+    Repr: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Kind, Repr> fmt::Display for Sequence<Kind, Repr>
+where
+    Repr: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Kind, Repr: Serialize> Serialize for Sequence<Kind, Repr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Kind, Repr> Deserialize<'de> for Sequence<Kind, Repr>
+where
+    Repr: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Repr::deserialize(deserializer).map(Sequence::<Kind, Repr>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Packet {}
+    type Seq = Sequence<Packet, u8>;
+
+    #[test]
+    fn test_rfc1982_comparison() {
+        let s = |x| Seq::from(x);
+
+        assert_eq!(s(0).serial_cmp(s(0)), Some(Ordering::Equal));
+        assert_eq!(s(0).serial_cmp(s(1)), Some(Ordering::Less));
+        assert_eq!(s(0).serial_cmp(s(127)), Some(Ordering::Less));
+        assert_eq!(s(0).serial_cmp(s(128)), None);
+        assert_eq!(s(0).serial_cmp(s(129)), Some(Ordering::Greater));
+        assert_eq!(s(255).serial_cmp(s(0)), Some(Ordering::Less));
+        assert_eq!(s(200).serial_cmp(s(100)), Some(Ordering::Greater));
+        assert_eq!(s(100).serial_cmp(s(200)), Some(Ordering::Less));
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// `Tagged<Tag, T>` attaches a compile-time tag to arbitrary data
+/// without giving it any arithmetics. It's handy for typestate-style
+/// tags, e.g. to tell validated input from raw input:
+///
+/// ```
+/// use phantom_newtype::Tagged;
+///
+/// enum Raw {}
+/// enum Validated {}
+///
+/// struct Email(String);
+///
+/// fn validate(email: Tagged<Raw, Email>) -> Result<Tagged<Validated, Email>, String> {
+///     let email = email.into_inner();
+///     if email.0.contains('@') {
+///         Ok(Tagged::new(email))
+///     } else {
+///         Err(format!("{} is not an email", email.0))
+///     }
+/// }
+///
+/// fn send(to: &Tagged<Validated, Email>) -> &str {
+///     &to.get().0
+/// }
+///
+/// let email = validate(Tagged::new(Email("me@example.com".to_string()))).unwrap();
+/// assert_eq!(send(&email), "me@example.com");
+/// assert!(validate(Tagged::new(Email("me".to_string()))).is_err());
+/// ```
+///
+/// Data with different tags can't be mixed:
+///
+/// ```compile_fail
+/// use phantom_newtype::Tagged;
+///
+/// enum Raw {}
+/// enum Validated {}
+///
+/// let raw: Tagged<Raw, String> = Tagged::new("<b>".to_string());
+/// let validated: Tagged<Validated, String> = raw;
+/// ```
+///
+/// The data can be modified or transformed without dropping the tag:
+///
+/// ```
+/// use phantom_newtype::Tagged;
+///
+/// enum Sanitized {}
+///
+/// let mut html = Tagged::<Sanitized, String>::new("&lt;b&gt;".to_string());
+/// html.get_mut().push_str("bold");
+///
+/// let len: Tagged<Sanitized, usize> = html.clone().map(|s| s.len());
+/// assert_eq!(html.get(), "&lt;b&gt;bold");
+/// assert_eq!(*len.get(), 13);
+/// ```
+///
+/// Tagged data is serialized as the data itself.
+///
+/// ```
+/// #[cfg(feature = "serde")] {
+/// use phantom_newtype::Tagged;
+/// use serde_json;
+///
+/// enum Validated {}
+/// type Emails = Tagged<Validated, Vec<String>>;
+///
+/// let emails = Emails::new(vec!["me@example.com".to_string()]);
+/// assert_eq!(serde_json::to_string(&emails).unwrap(), r#"["me@example.com"]"#);
+/// assert_eq!(serde_json::from_str::<Emails>(r#"["me@example.com"]"#).unwrap(), emails);
+/// }
+/// ```
+pub struct Tagged<Tag, T>(T, PhantomData<std::sync::Mutex<Tag>>);
+
+impl<Tag, T> Tagged<Tag, T> {
+    /// `new` is a synonym for `from` that can be evaluated in
+    /// compile time.
+    pub const fn new(value: T) -> Tagged<Tag, T> {
+        Tagged(value, PhantomData)
+    }
+
+    /// Returns a reference to the tagged data.
+    pub const fn get(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the tagged data.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Removes the tag and returns the data.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Transforms the tagged data keeping the tag.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Tagged<Tag, U> {
+        Tagged::new(f(self.0))
+    }
+}
+
+impl<Tag, T> From<T> for Tagged<Tag, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<Tag, T: Default> Default for Tagged<Tag, T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<Tag, T: Clone> Clone for Tagged<Tag, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<Tag, T: Copy> Copy for Tagged<Tag, T> {}
+
+impl<Tag, T: PartialEq> PartialEq for Tagged<Tag, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Tag, T: Eq> Eq for Tagged<Tag, T> {}
+
+impl<Tag, T: PartialOrd> PartialOrd for Tagged<Tag, T> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<Tag, T: Ord> Ord for Tagged<Tag, T> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<Tag, T: Hash> Hash for Tagged<Tag, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Tag, T: fmt::Debug> fmt::Debug for Tagged<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Tag, T: Serialize> Serialize for Tagged<Tag, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Tag, T> Deserialize<'de> for Tagged<Tag, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Tagged::<Tag, T>::new)
+    }
+}
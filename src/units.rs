@@ -0,0 +1,182 @@
+// Copyright 2019 DFINITY
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+/// A marker trait for units that take part in the opt-in type-level
+/// unit algebra.
+///
+/// Products and quotients of base units are expressed with
+/// [`Times`](struct.Times.html) and [`Per`](struct.Per.html), the
+/// exponent of a unit is the number of times it occurs in the type.
+/// Multiplying amounts yields an amount of the product unit, dividing
+/// amounts of different units is done with
+/// [`Amount::divide`](struct.Amount.html#method.divide).
+///
+/// The algebra is purely structural: units are never reordered, and
+/// only the last factor of a product or of a denominator cancels out.
+/// The following rules are provided, `a`, `b` and `c` stand for base
+/// units, `X` and `Y` stand for arbitrary units:
+///
+/// | Operation                   | Result                      |
+/// |-----------------------------|-----------------------------|
+/// | `a * b`                     | `Times<a, b>`               |
+/// | `Times<X, Y> * c`           | `Times<Times<X, Y>, c>`     |
+/// | `Per<X, b> * b`             | `X`                         |
+/// | `Per<X, Times<Y, c>> * c`   | `Per<X, Y>`                 |
+/// | `a / b`                     | `Per<a, b>`                 |
+/// | `Times<X, b> / b`           | `X`                         |
+/// | `Per<X, b> / c`             | `Per<X, Times<b, c>>`       |
+///
+/// ```
+/// use phantom_newtype::{Amount, BaseUnit, Per, Times};
+///
+/// enum Meters {}
+/// enum Seconds {}
+///
+/// impl BaseUnit for Meters {}
+/// impl BaseUnit for Seconds {}
+///
+/// type Length = Amount<Meters, u64>;
+/// type Area = Amount<Times<Meters, Meters>, u64>;
+/// type Time = Amount<Seconds, u64>;
+/// type Speed = Amount<Per<Meters, Seconds>, u64>;
+///
+/// assert_eq!(Length::from(3) * Length::from(4), Area::from(12));
+/// assert_eq!(Area::from(12).divide(Length::from(4)), Length::from(3));
+///
+/// let speed: Speed = Length::from(100).divide(Time::from(20));
+/// assert_eq!(speed, Speed::from(5));
+/// assert_eq!(speed * Time::from(3), Length::from(15));
+/// ```
+///
+/// Products are not commutative, `Times<Meters, Seconds>` and
+/// `Times<Seconds, Meters>` are different units:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Amount, BaseUnit, Times};
+///
+/// enum Meters {}
+/// enum Seconds {}
+///
+/// impl BaseUnit for Meters {}
+/// impl BaseUnit for Seconds {}
+///
+/// let _: Amount<Times<Seconds, Meters>, u64> =
+///     Amount::<Meters, u64>::from(3) * Amount::<Seconds, u64>::from(4);
+/// ```
+///
+/// Amounts of the same unit should be divided with the `/` operator
+/// that produces a bare `Repr`, `divide` would produce an amount of
+/// `Per<a, a>` instead.
+///
+/// If all you need is a quotient of two units that is only multiplied
+/// back by its denominator, consider [`Rate`](struct.Rate.html): it
+/// works with any markers and doesn't require implementing `BaseUnit`.
+/// Use `Per` when the quotient takes part in further products and
+/// quotients.
+///
+/// Units that don't implement `BaseUnit` can't be multiplied:
+///
+/// ```compile_fail
+/// use phantom_newtype::Amount;
+///
+/// enum Meters {}
+/// type Length = Amount<Meters, u64>;
+///
+/// let _ = Length::from(3) * Length::from(4);
+/// ```
+pub trait BaseUnit {}
+
+/// A product of units `A` and `B`.
+///
+/// This type is only used as a marker and can't be constructed.
+pub struct Times<A, B>(PhantomData<(A, B)>);
+
+/// A quotient of units `A` and `B`.
+///
+/// This type is only used as a marker and can't be constructed.
+pub struct Per<A, B>(PhantomData<(A, B)>);
+
+/// Describes the unit obtained by multiplying `Self` by `Rhs`.
+pub trait UnitMul<Rhs> {
+    type Output;
+}
+
+/// Describes the unit obtained by dividing `Self` by `Rhs`.
+pub trait UnitDiv<Rhs> {
+    type Output;
+}
+
+impl<A: BaseUnit, B: BaseUnit> UnitMul<B> for A {
+    type Output = Times<A, B>;
+}
+
+impl<X, Y, C: BaseUnit> UnitMul<C> for Times<X, Y> {
+    type Output = Times<Times<X, Y>, C>;
+}
+
+impl<X, B: BaseUnit> UnitMul<B> for Per<X, B> {
+    type Output = X;
+}
+
+impl<X, Y, C: BaseUnit> UnitMul<C> for Per<X, Times<Y, C>> {
+    type Output = Per<X, Y>;
+}
+
+impl<A: BaseUnit, B: BaseUnit> UnitDiv<B> for A {
+    type Output = Per<A, B>;
+}
+
+impl<X, B: BaseUnit> UnitDiv<B> for Times<X, B> {
+    type Output = X;
+}
+
+impl<X, B, C: BaseUnit> UnitDiv<C> for Per<X, B> {
+    type Output = Per<X, Times<B, C>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Amount, BaseUnit, Per, Times};
+
+    enum Meters {}
+    enum Seconds {}
+
+    impl BaseUnit for Meters {}
+    impl BaseUnit for Seconds {}
+
+    type Length = Amount<Meters, i64>;
+    type Time = Amount<Seconds, i64>;
+    type Speed = Amount<Per<Meters, Seconds>, i64>;
+    type Acceleration = Amount<Per<Meters, Times<Seconds, Seconds>>, i64>;
+    type Volume = Amount<Times<Times<Meters, Meters>, Meters>, i64>;
+
+    #[test]
+    fn test_unit_algebra() {
+        let length = Length::from(120);
+        let time = Time::from(4);
+
+        let speed: Speed = length.divide(time);
+        assert_eq!(speed, Speed::from(30));
+
+        let acceleration: Acceleration = speed.divide(time);
+        assert_eq!(acceleration, Acceleration::from(7));
+        assert_eq!(acceleration * time, Speed::from(28));
+        assert_eq!(speed * time, length);
+
+        let volume: Volume = length * length * length;
+        assert_eq!(volume.divide(length), length * length);
+    }
+}